@@ -103,6 +103,7 @@ fn query_decls(route: &Route) -> Option<TokenStream> {
 
             if !__e.is_empty() {
                 #_log::warn_!("Query string failed to match route declaration.");
+                #__req._trace_guard_forward(::std::format_args!("query string: {}", __e));
                 for _err in __e { #_log::warn_!("{}", _err); }
                 return #Outcome::Forward(#__data);
             }
@@ -123,6 +124,9 @@ fn request_guard_decl(guard: &Guard) -> TokenStream {
             #Outcome::Success(__v) => __v,
             #Outcome::Forward(_) => {
                 #_log::warn_!("Request guard `{}` is forwarding.", stringify!(#ty));
+                #__req._trace_guard_forward(
+                    ::std::format_args!("request guard `{}`", stringify!(#ty)));
+
                 return #Outcome::Forward(#__data);
             },
             #Outcome::Failure((__c, __e)) => {
@@ -145,6 +149,9 @@ fn param_guard_decl(guard: &Guard) -> TokenStream {
         #_log::warn_!("Parameter guard `{}: {}` is forwarding: {:?}.",
             #name, stringify!(#ty), __error);
 
+        #__req._trace_guard_forward(::std::format_args!("parameter guard `{}: {}`: {:?}",
+            #name, stringify!(#ty), __error));
+
         #Outcome::Forward(#__data)
     });
 
@@ -186,6 +193,9 @@ fn data_guard_decl(guard: &Guard) -> TokenStream {
             #Outcome::Success(__d) => __d,
             #Outcome::Forward(__d) => {
                 #_log::warn_!("Data guard `{}` is forwarding.", stringify!(#ty));
+                #__req._trace_guard_forward(
+                    ::std::format_args!("data guard `{}`", stringify!(#ty)));
+
                 return #Outcome::Forward(__d);
             }
             #Outcome::Failure((__c, __e)) => {
@@ -332,6 +342,8 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     let uri = route.attr.uri.to_string();
     let rank = Optional(route.attr.rank);
     let format = Optional(route.attr.format.as_ref());
+    let limit = Optional(route.attr.limit.as_deref());
+    let auto_head = route.attr.auto_head.unwrap_or(true);
 
     Ok(quote! {
         #handler_fn
@@ -366,6 +378,8 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
                     handler: monomorphized_function,
                     format: #format,
                     rank: #rank,
+                    limit: #limit,
+                    auto_head: #auto_head,
                     sentinels: #sentinels,
                 }
             }
@@ -418,6 +432,8 @@ fn incomplete_route(
         data: method_attribute.data,
         format: method_attribute.format,
         rank: method_attribute.rank,
+        limit: method_attribute.limit,
+        auto_head: method_attribute.auto_head,
     };
 
     codegen_route(Route::from(attribute, function)?)
@@ -157,14 +157,18 @@ macro_rules! route_attribute {
         /// parameter := 'rank' '=' INTEGER
         ///            | 'format' '=' '"' MEDIA_TYPE '"'
         ///            | 'data' '=' '"' SINGLE_PARAM '"'
+        ///            | 'limit' '=' '"' LIMIT_NAME '"'
+        ///            | 'auto_head' '=' BOOL
         ///
         /// SINGLE_PARAM := '<' IDENT '>'
         /// TRAILING_PARAM := '<' IDENT '..>'
         ///
         /// URI_SEG := valid, non-percent-encoded HTTP URI segment
         /// MEDIA_TYPE := valid HTTP media type or known shorthand
+        /// LIMIT_NAME := name of a data limit in the configured `limits`
         ///
         /// INTEGER := unsigned integer, as defined by Rust
+        /// BOOL := `true` or `false`
         /// IDENT := valid identifier, as defined by Rust
         /// ```
         ///
@@ -258,9 +262,10 @@ macro_rules! route_attribute {
         ///   2. A static structure used by [`routes!`] to generate a [`Route`].
         ///
         ///      The static structure (and resulting [`Route`]) is populated
-        ///      with the name (the function's name), path, query, rank, and
-        ///      format from the route attribute. The handler is set to the
-        ///      generated handler.
+        ///      with the name (the function's name), path, query, rank,
+        ///      format, data limit name, and `HEAD` autohandling preference
+        ///      from the route attribute. The handler is set to the generated
+        ///      handler.
         ///
         ///   3. A macro used by [`uri!`] to type-check and generate an
         ///      [`Origin`].
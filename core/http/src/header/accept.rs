@@ -243,6 +243,65 @@ impl Accept {
         self.iter().map(|weighted_mt| weighted_mt.media_type())
     }
 
+    /// Returns the media type in `offered` that the client most prefers, or
+    /// `None` if the client accepts none of them. This method follows [RFC
+    /// 7231 5.3.2].
+    ///
+    /// The quality of an offered media type is that of the most specific media
+    /// range in `self` matching it, where `text/html` is more specific than
+    /// `text/*`, which is more specific than `*/*`. A range without a quality
+    /// value has a quality of `1`. Media types matching no range, or whose
+    /// most specific range has a quality of `0`, are not acceptable. Of the
+    /// media types with the greatest quality, the earliest in `offered` is
+    /// returned. If `self` is empty, every media type is acceptable.
+    ///
+    /// [RFC 7231 5.3.2]: https://tools.ietf.org/html/rfc7231#section-5.3.2
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Accept, MediaType};
+    ///
+    /// let offered = [MediaType::HTML, MediaType::JSON, MediaType::Plain];
+    ///
+    /// let accept: Accept = "application/json, text/*; q=0.5".parse().unwrap();
+    /// assert_eq!(accept.negotiate(&offered), Some(&MediaType::JSON));
+    ///
+    /// let accept: Accept = "text/*, text/html; q=0".parse().unwrap();
+    /// assert_eq!(accept.negotiate(&offered), Some(&MediaType::Plain));
+    ///
+    /// let accept: Accept = "image/png".parse().unwrap();
+    /// assert_eq!(accept.negotiate(&offered), None);
+    /// ```
+    pub fn negotiate<'m>(&self, offered: &'m [MediaType]) -> Option<&'m MediaType> {
+        fn matches(range: &MediaType, media_type: &MediaType) -> bool {
+            (range.top() == "*" || range.top() == media_type.top())
+                && (range.sub() == "*" || range.sub() == media_type.sub())
+        }
+
+        let quality = |media_type: &MediaType| -> f32 {
+            if self.iter().next().is_none() {
+                return 1.0;
+            }
+
+            self.iter()
+                .filter(|range| matches(range.media_type(), media_type))
+                .max_by_key(|range| range.specificity())
+                .map_or(0.0, |range| range.weight_or(1.0))
+        };
+
+        let mut best: Option<(&MediaType, f32)> = None;
+        for media_type in offered {
+            let q = quality(media_type);
+            if q > 0.0 && best.map_or(true, |(_, best_q)| q > best_q) {
+                best = Some((media_type, q));
+            }
+        }
+
+        best.map(|(media_type, _)| media_type)
+    }
+
     known_media_types!(accept_constructor);
 }
 
@@ -423,4 +482,33 @@ mod test {
         assert_preference("a/b; q=0.5; v=1, a/b; q=0.5; v=1; c=2", "a/b; q=0.5; v=1; c=2");
         assert_preference("a/b; q=0.6; v=1, a/b; q=0.5; v=1; c=2", "a/b; q=0.6; v=1");
     }
+
+    #[track_caller]
+    fn assert_negotiated(string: &str, offered: &[MediaType], expect: Option<MediaType>) {
+        let accept: Accept = string.parse().expect("accept string parse");
+        let actual = accept.negotiate(offered);
+        if actual != expect.as_ref() {
+            panic!("mismatch for {}: expected {:?}, got {:?}", string, expect, actual)
+        }
+    }
+
+    #[test]
+    fn test_negotiate() {
+        use MediaType as M;
+
+        let offered = [M::HTML, M::JSON, M::Plain];
+        assert_negotiated("*/*", &offered, Some(M::HTML));
+        assert_negotiated("application/json", &offered, Some(M::JSON));
+        assert_negotiated("text/plain", &offered, Some(M::Plain));
+        assert_negotiated("text/*", &offered, Some(M::HTML));
+        assert_negotiated("text/html; q=0.5, application/json", &offered, Some(M::JSON));
+        assert_negotiated("text/html, application/json", &offered, Some(M::HTML));
+        assert_negotiated("application/json, text/html", &offered, Some(M::HTML));
+        assert_negotiated("*/*; q=0.1, text/plain", &offered, Some(M::Plain));
+        assert_negotiated("text/*, text/html; q=0", &offered, Some(M::Plain));
+        assert_negotiated("*/*, application/json; q=0", &[M::JSON], None);
+        assert_negotiated("application/*; q=0.2, */*; q=0.1", &offered, Some(M::JSON));
+        assert_negotiated("image/png", &offered, None);
+        assert_negotiated("text/html", &[], None);
+    }
 }
@@ -391,3 +391,27 @@ impl From<ContentType> for Header<'static> {
         }
     }
 }
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::ContentType;
+
+    use serde_::ser::{Serialize, Serializer};
+    use serde_::de::{Deserialize, Deserializer, Error};
+
+    /// Serializes as the HTTP rendering of the Content-Type.
+    impl Serialize for ContentType {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    /// Deserializes from a string as parsed by [`ContentType::from_str()`].
+    ///
+    /// [`ContentType::from_str()`]: std::str::FromStr::from_str()
+    impl<'de> Deserialize<'de> for ContentType {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+        }
+    }
+}
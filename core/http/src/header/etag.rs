@@ -0,0 +1,442 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Header;
+
+/// Representation of an HTTP entity tag, as sent in `ETag` headers.
+///
+/// An entity tag is an opaque string, the _tag_, which is either _strong_ or
+/// _weak_. A weak tag is rendered with a `W/` prefix, as in `W/"abc"`.
+///
+/// # Comparison
+///
+/// [RFC 7232 §2.3.2] defines two ways to compare entity tags:
+///
+///   * [`EntityTag::strong_eq()`]: both tags are strong and equal. Used for
+///     `If-Match` and `If-Range`.
+///   * [`EntityTag::weak_eq()`]: the tags are equal, whether weak or not.
+///     Used for `If-None-Match`.
+///
+/// Because equality is ambiguous, `EntityTag` doesn't implement
+/// `PartialEq`; use one of the methods above instead.
+///
+/// # Scope
+///
+/// Rocket doesn't itself evaluate conditional requests: no built-in
+/// responder, including `NamedFile`, sets an `ETag` or honors `If-Match`,
+/// `If-None-Match`, or `If-Range`. `EntityTag` and [`EntityTags`] provide the
+/// parsing and comparisons a responder that does so needs.
+///
+/// [RFC 7232 §2.3.2]: https://tools.ietf.org/html/rfc7232#section-2.3.2
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::EntityTag;
+///
+/// let weak: EntityTag = "W/\"v1\"".parse().unwrap();
+/// let strong = EntityTag::strong("v1");
+///
+/// assert!(weak.weak_eq(&strong));
+/// assert!(!weak.strong_eq(&strong));
+/// assert!(strong.strong_eq(&EntityTag::strong("v1")));
+/// ```
+///
+/// # Header
+///
+/// `EntityTag` implements `Into<Header>` as an `ETag` header:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::EntityTag;
+/// use rocket::response::Response;
+///
+/// let response = Response::build().header(EntityTag::weak("v1")).finalize();
+/// assert_eq!(response.headers().get_one("ETag"), Some("W/\"v1\""));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EntityTag {
+    weak: bool,
+    tag: String,
+}
+
+impl EntityTag {
+    /// Creates a new strong entity tag with the opaque tag `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` contains a character not allowed in entity tags: a
+    /// control character, a space, or a double quote.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let etag = EntityTag::strong("xyzzy");
+    /// assert!(!etag.is_weak());
+    /// assert_eq!(etag.to_string(), "\"xyzzy\"");
+    /// ```
+    pub fn strong<T: Into<String>>(tag: T) -> EntityTag {
+        EntityTag::new(false, tag.into())
+    }
+
+    /// Creates a new weak entity tag with the opaque tag `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` contains a character not allowed in entity tags: a
+    /// control character, a space, or a double quote.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let etag = EntityTag::weak("xyzzy");
+    /// assert!(etag.is_weak());
+    /// assert_eq!(etag.to_string(), "W/\"xyzzy\"");
+    /// ```
+    pub fn weak<T: Into<String>>(tag: T) -> EntityTag {
+        EntityTag::new(true, tag.into())
+    }
+
+    fn new(weak: bool, tag: String) -> EntityTag {
+        assert!(tag.chars().all(is_etagc), "invalid entity tag: {:?}", tag);
+        EntityTag { weak, tag }
+    }
+
+    /// Returns the opaque tag, without quotes or a weakness prefix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let etag: EntityTag = "W/\"xyzzy\"".parse().unwrap();
+    /// assert_eq!(etag.tag(), "xyzzy");
+    /// ```
+    #[inline(always)]
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns `true` if `self` is a weak entity tag.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// assert!(EntityTag::weak("xyzzy").is_weak());
+    /// assert!(!EntityTag::strong("xyzzy").is_weak());
+    /// ```
+    #[inline(always)]
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Compares `self` and `other` using the _strong_ comparison function:
+    /// returns `true` if neither tag is weak and the tags are identical.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// assert!(EntityTag::strong("1").strong_eq(&EntityTag::strong("1")));
+    /// assert!(!EntityTag::weak("1").strong_eq(&EntityTag::strong("1")));
+    /// assert!(!EntityTag::weak("1").strong_eq(&EntityTag::weak("1")));
+    /// ```
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Compares `self` and `other` using the _weak_ comparison function:
+    /// returns `true` if the tags are identical, whether weak or not.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// assert!(EntityTag::weak("1").weak_eq(&EntityTag::strong("1")));
+    /// assert!(EntityTag::weak("1").weak_eq(&EntityTag::weak("1")));
+    /// assert!(!EntityTag::weak("1").weak_eq(&EntityTag::weak("2")));
+    /// ```
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.tag == other.tag
+    }
+
+    /// Parses the value of an `If-Match` or `If-None-Match` header: either
+    /// `*` or a comma-separated list of entity tags. Returns `None` if any
+    /// entry is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{EntityTag, EntityTags};
+    ///
+    /// let tags = EntityTag::parse_list("\"a\", W/\"b\"").unwrap();
+    /// assert!(tags.weak_matches(&EntityTag::strong("b")));
+    /// assert!(!tags.strong_matches(&EntityTag::strong("b")));
+    ///
+    /// assert!(matches!(EntityTag::parse_list(" * "), Some(EntityTags::Any)));
+    /// assert!(EntityTag::parse_list("\"a\", *").is_none());
+    /// ```
+    pub fn parse_list(value: &str) -> Option<EntityTags> {
+        fn is_ows(c: char) -> bool { c == ' ' || c == '\t' }
+
+        if value.trim_matches(is_ows) == "*" {
+            return Some(EntityTags::Any);
+        }
+
+        // Tags may contain `,`, so each entry extends to its closing quote.
+        let mut tags = vec![];
+        let mut rest = value;
+        loop {
+            rest = rest.trim_start_matches(|c| c == ',' || is_ows(c));
+            if rest.is_empty() {
+                return Some(EntityTags::Tags(tags));
+            }
+
+            let opaque = if rest.starts_with("W/") { 2 } else { 0 };
+            if !rest[opaque..].starts_with('"') {
+                return None;
+            }
+
+            let end = opaque + 1 + rest[(opaque + 1)..].find('"')? + 1;
+            tags.push(rest[..end].parse().ok()?);
+            rest = rest[end..].trim_start_matches(is_ows);
+            if !rest.is_empty() && !rest.starts_with(',') {
+                return None;
+            }
+        }
+    }
+}
+
+/// The value of an `If-Match` or `If-None-Match` header, as parsed by
+/// [`EntityTag::parse_list()`].
+///
+/// # Example
+///
+/// Evaluating `If-None-Match`, which uses the weak comparison function:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::EntityTag;
+///
+/// let current = EntityTag::strong("v2");
+/// let if_none_match = EntityTag::parse_list("W/\"v1\", W/\"v2\"").unwrap();
+/// let not_modified = if_none_match.weak_matches(&current);
+/// assert!(not_modified);
+/// ```
+#[derive(Debug, Clone)]
+pub enum EntityTags {
+    /// `*`: matches any current entity tag.
+    Any,
+    /// A list of entity tags, possibly empty.
+    Tags(Vec<EntityTag>),
+}
+
+impl EntityTags {
+    /// Returns `true` if `self` is `*` or contains a tag equal to `etag`
+    /// under the strong comparison function. Used for `If-Match`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let tags = EntityTag::parse_list("\"a\", W/\"b\"").unwrap();
+    /// assert!(tags.strong_matches(&EntityTag::strong("a")));
+    /// assert!(!tags.strong_matches(&EntityTag::strong("b")));
+    /// ```
+    pub fn strong_matches(&self, etag: &EntityTag) -> bool {
+        match self {
+            EntityTags::Any => true,
+            EntityTags::Tags(tags) => tags.iter().any(|t| t.strong_eq(etag)),
+        }
+    }
+
+    /// Returns `true` if `self` is `*` or contains a tag equal to `etag`
+    /// under the weak comparison function. Used for `If-None-Match`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let tags = EntityTag::parse_list("*").unwrap();
+    /// assert!(tags.weak_matches(&EntityTag::weak("anything")));
+    /// ```
+    pub fn weak_matches(&self, etag: &EntityTag) -> bool {
+        match self {
+            EntityTags::Any => true,
+            EntityTags::Tags(tags) => tags.iter().any(|t| t.weak_eq(etag)),
+        }
+    }
+}
+
+/// Returns `true` if `c` is an `etagc`: `%x21 / %x23-7E / obs-text`.
+fn is_etagc(c: char) -> bool {
+    c == '\x21' || ('\x23'..='\x7E').contains(&c) || !c.is_ascii()
+}
+
+impl FromStr for EntityTag {
+    type Err = String;
+
+    /// Parses an `EntityTag` from an `ETag` header value: an optional `W/`
+    /// followed by a double-quoted opaque tag.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let etag: EntityTag = "\"xyzzy\"".parse().unwrap();
+    /// assert!(!etag.is_weak());
+    ///
+    /// assert!("xyzzy".parse::<EntityTag>().is_err());
+    /// assert!("w/\"xyzzy\"".parse::<EntityTag>().is_err());
+    /// ```
+    fn from_str(raw: &str) -> Result<EntityTag, String> {
+        let (weak, quoted) = match raw.strip_prefix("W/") {
+            Some(rest) => (true, rest),
+            None => (false, raw),
+        };
+
+        quoted.strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+            .filter(|tag| tag.chars().all(is_etagc))
+            .map(|tag| EntityTag { weak, tag: tag.into() })
+            .ok_or_else(|| format!("invalid entity tag: {:?}", raw))
+    }
+}
+
+impl fmt::Display for EntityTag {
+    /// Formats the entity tag as an HTTP `ETag` value.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/")?;
+        }
+
+        write!(f, "\"{}\"", self.tag)
+    }
+}
+
+/// Creates a new `Header` with name `ETag` and the value set to the HTTP
+/// rendering of this entity tag.
+impl From<EntityTag> for Header<'static> {
+    #[inline(always)]
+    fn from(etag: EntityTag) -> Self {
+        Header::new("ETag", etag.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EntityTag, EntityTags};
+
+    fn etag(string: &str) -> EntityTag {
+        string.parse().expect("entity tag parse")
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(!etag("\"\"").is_weak());
+        assert_eq!(etag("\"\"").tag(), "");
+        assert_eq!(etag("W/\"a-b_c\"").tag(), "a-b_c");
+        assert_eq!(etag("\"ünïcödé\"").tag(), "ünïcödé");
+
+        for bad in &["", "\"", "a", "W/a", "W/\"", "\"a", "a\"", "w/\"a\"", "\"a b\"", "\"a\"b\""] {
+            assert!(bad.parse::<EntityTag>().is_err(), "{:?} parsed", bad);
+        }
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for string in &["\"\"", "\"xyzzy\"", "W/\"xyzzy\""] {
+            assert_eq!(etag(string).to_string(), *string);
+        }
+    }
+
+    // The comparison table in RFC 7232 §2.3.2.
+    #[test]
+    fn test_comparison() {
+        let cases = [
+            ("W/\"1\"", "W/\"1\"", false, true),
+            ("W/\"1\"", "W/\"2\"", false, false),
+            ("W/\"1\"", "\"1\"", false, true),
+            ("\"1\"", "\"1\"", true, true),
+            ("\"1\"", "\"2\"", false, false),
+        ];
+
+        for &(a, b, strong, weak) in &cases {
+            assert_eq!(etag(a).strong_eq(&etag(b)), strong, "{} strong {}", a, b);
+            assert_eq!(etag(b).strong_eq(&etag(a)), strong, "{} strong {}", b, a);
+            assert_eq!(etag(a).weak_eq(&etag(b)), weak, "{} weak {}", a, b);
+            assert_eq!(etag(b).weak_eq(&etag(a)), weak, "{} weak {}", b, a);
+        }
+    }
+
+    fn list(string: &str) -> Vec<(bool, String)> {
+        match EntityTag::parse_list(string).expect("entity tag list parse") {
+            EntityTags::Tags(tags) => tags.iter().map(|t| (t.is_weak(), t.tag().into())).collect(),
+            EntityTags::Any => panic!("{:?} parsed as `*`", string),
+        }
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let tags = [(false, "a".into()), (true, "b".into()), (false, "c".into())];
+        assert_eq!(list("\"a\", W/\"b\",\"c\""), tags);
+        assert_eq!(list("\"a,b\", W/\"c\""), [(false, "a,b".into()), (true, "c".into())]);
+        assert_eq!(list(""), []);
+
+        assert!(matches!(EntityTag::parse_list("*"), Some(EntityTags::Any)));
+        assert!(matches!(EntityTag::parse_list("\t* "), Some(EntityTags::Any)));
+
+        assert!(EntityTag::parse_list("\"a\", b").is_none());
+        assert!(EntityTag::parse_list("\"a\"\"b\"").is_none());
+        assert!(EntityTag::parse_list("\"a, b").is_none());
+        assert!(EntityTag::parse_list("*, \"a\"").is_none());
+        assert!(EntityTag::parse_list("**").is_none());
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let current = etag("\"v1\"");
+        assert!(EntityTag::parse_list("W/\"v1\"").unwrap().weak_matches(&current));
+        assert!(EntityTag::parse_list("\"v0\", \"v1\"").unwrap().weak_matches(&current));
+        assert!(EntityTag::parse_list("*").unwrap().weak_matches(&current));
+        assert!(!EntityTag::parse_list("W/\"v0\"").unwrap().weak_matches(&current));
+    }
+
+    // `If-Range` holds a single tag compared strongly: a weak validator never
+    // matches, so the range is ignored and the full representation is sent.
+    #[test]
+    fn test_if_range() {
+        let if_range = |current: &str, value: &str| etag(value).strong_eq(&etag(current));
+        assert!(if_range("\"v1\"", "\"v1\""));
+        assert!(!if_range("\"v1\"", "W/\"v1\""));
+        assert!(!if_range("W/\"v1\"", "\"v1\""));
+        assert!(!if_range("W/\"v1\"", "W/\"v1\""));
+        assert!(!if_range("\"v1\"", "\"v2\""));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_tag_panics() {
+        EntityTag::strong("with \"quotes\"");
+    }
+}
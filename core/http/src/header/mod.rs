@@ -3,11 +3,13 @@ mod known_media_types;
 mod media_type;
 mod content_type;
 mod accept;
+mod etag;
 mod header;
 
 pub use self::content_type::ContentType;
 pub use self::accept::{Accept, QMediaType};
 pub use self::media_type::MediaType;
+pub use self::etag::{EntityTag, EntityTags};
 pub use self::header::{Header, HeaderMap};
 
 pub(crate) use self::media_type::Source;
@@ -47,7 +47,7 @@ impl Certificates {
 
 // TODO.async: 'Listener' and 'Connection' provide common enough functionality
 // that they could be introduced in upstream libraries.
-/// A 'Listener' yields incoming connections
+/// A `Listener` yields incoming connections.
 pub trait Listener {
     /// The connection type returned by this listener.
     type Connection: Connection;
@@ -63,7 +63,7 @@ pub trait Listener {
     ) -> Poll<io::Result<Self::Connection>>;
 }
 
-/// A 'Connection' represents an open connection to a client
+/// A `Connection` represents an open connection to a client.
 pub trait Connection: AsyncRead + AsyncWrite {
     /// The remote address, i.e. the client's socket address, if it is known.
     fn peer_address(&self) -> Option<SocketAddr>;
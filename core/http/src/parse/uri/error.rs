@@ -27,6 +27,10 @@ impl<'a> From<ParseError<RawInput<'a>>> for Error<'a> {
 }
 
 impl Error<'_> {
+    pub(crate) fn other(expected: &'static str, index: usize) -> Self {
+        Error { expected: expected.into(), index }
+    }
+
     /// Returns the byte index into the text where the error occurred if it is
     /// known.
     ///
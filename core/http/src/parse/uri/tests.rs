@@ -232,6 +232,30 @@ fn absolute() {
     };
 }
 
+#[test]
+fn absolute_strict() {
+    for uri in ["http://a.com:8000", "http://u:p@a.com/b?c", "foo:/a/b", "http:/", "a://b:?q"] {
+        let lenient = Absolute::parse(uri).unwrap();
+        assert_eq!(Absolute::parse_strict(uri).unwrap(), lenient, "{}", uri);
+    }
+
+    let rejected = [
+        ("http://", 7), ("http:///a", 7), ("http://:80", 7), ("http://?q", 7),
+        ("http://user@", 12), ("http://u:p@:80/a", 11), ("foo:bar", 4),
+        ("localhost:8080", 10), ("user:pass@host.com", 5), ("ftp:::", 4), ("foo:", 4),
+    ];
+
+    for (uri, index) in rejected {
+        assert!(Absolute::parse(uri).is_ok(), "{} not lenient", uri);
+        let error = Absolute::parse_strict(uri).unwrap_err();
+        assert_eq!(error.index(), index, "{}: {}", uri, error);
+    }
+
+    let error = Absolute::parse_strict("http://").unwrap_err();
+    assert_eq!(error.to_string(), "expected non-empty host at index 7");
+    assert!(Absolute::parse_strict("not a uri").is_err());
+}
+
 #[test]
 fn reference() {
     assert_parse_eq!(
@@ -46,8 +46,9 @@ impl StatusClass {
 ///
 /// A `Status` should rarely be created directly. Instead, an associated
 /// constant should be used; one is declared for every status defined in the
-/// HTTP standard. If a custom status code _must_ be created, note that it is
-/// not possible to set a custom reason phrase.
+/// HTTP standard. If a custom status code _must_ be created, note that a
+/// `Status` does not carry a custom reason phrase; to set one, use
+/// `Response::set_reason_phrase()`.
 ///
 /// ```rust
 /// # extern crate rocket;
@@ -2,7 +2,7 @@ use std::borrow::Cow;
 
 use crate::ext::IntoOwned;
 use crate::parse::{Extent, IndexedStr};
-use crate::uri::{Authority, Path, Query, Data, Error, as_utf8_unchecked, fmt};
+use crate::uri::{Authority, Origin, Path, Query, Data, Error, as_utf8_unchecked, fmt};
 
 /// A URI with a scheme, authority, path, and query.
 ///
@@ -124,6 +124,53 @@ impl<'a> Absolute<'a> {
         crate::parse::uri::absolute_from_str(string)
     }
 
+    /// Parses the string `string` into an `Absolute`, strictly. Parsing will
+    /// never allocate. Returns an `Error` if `string` is not a valid absolute
+    /// URI or if it is not a hierarchical URI with a host or an absolute path.
+    ///
+    /// [`Absolute::parse()`] accepts every absolute URI RFC 3986 allows,
+    /// including forms that are rarely intended when a URL is expected, such
+    /// as a host and port without a scheme (`localhost:8080` parses with a
+    /// scheme of `localhost`) or an authority without a host (`http://` or
+    /// `http://user@`). This method additionally requires that:
+    ///
+    ///   * If there is an authority, its host is non-empty.
+    ///   * If there is no authority, the path is absolute: it begins with `/`.
+    ///
+    /// As a result, URIs with a rootless path, such as `mailto:me@rocket.rs`,
+    /// are rejected. This makes `parse_strict()` suitable for validating
+    /// user-supplied URLs, such as webhook targets, but not opaque URIs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::uri::Absolute;
+    ///
+    /// let uri = Absolute::parse_strict("https://rocket.rs/hook").expect("valid URI");
+    /// assert_eq!(uri.authority().unwrap().host(), "rocket.rs");
+    ///
+    /// // These are accepted by `parse()` but rejected by `parse_strict()`.
+    /// for uri in ["localhost:8080", "http://", "http://user@", "http://:80/a"] {
+    ///     assert!(Absolute::parse(uri).is_ok());
+    ///     assert!(Absolute::parse_strict(uri).is_err());
+    /// }
+    /// ```
+    pub fn parse_strict(string: &'a str) -> Result<Absolute<'a>, Error<'a>> {
+        let absolute = Absolute::parse(string)?;
+        let hier_part = absolute.scheme().len() + 1;
+        match absolute.authority() {
+            Some(authority) if authority.host().is_empty() => {
+                let index = hier_part + 2 + authority.user_info().map_or(0, |u| u.len() + 1);
+                Err(Error::other("expected non-empty host", index))
+            }
+            None if !absolute.path().starts_with('/') => {
+                Err(Error::other("expected '//' or '/' after scheme", hier_part))
+            }
+            _ => Ok(absolute),
+        }
+    }
+
     /// Parses the string `string` into an `Absolute`. Allocates minimally on
     /// success and error.
     ///
@@ -227,6 +274,58 @@ impl<'a> Absolute<'a> {
         self.query.as_ref().map(|data| Query { source: &self.source, data })
     }
 
+    /// Returns the first url-decoded value for the query parameter named
+    /// `key`, if there is one.
+    ///
+    /// The query is decoded as `application/x-www-form-urlencoded`: `+` is
+    /// decoded as a space and percent-encoded characters are decoded in both
+    /// names and values. A parameter without a `=` has an empty value. `key`
+    /// is compared against decoded names and is itself not decoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let uri = uri!("https://rocket.rs/search?q=rocket+web&page=2&page=3&debug");
+    /// assert_eq!(uri.query_value("q"), Some("rocket web"));
+    /// assert_eq!(uri.query_value("page"), Some("2"));
+    /// assert_eq!(uri.query_value("debug"), Some(""));
+    /// assert_eq!(uri.query_value("lang"), None);
+    ///
+    /// let uri = uri!("https://rocket.rs/search");
+    /// assert_eq!(uri.query_value("q"), None);
+    /// ```
+    #[inline]
+    pub fn query_value(&self, key: &str) -> Option<&str> {
+        self.query()?
+            .segments()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over all of the url-decoded values, in order, for
+    /// the query parameter named `key`. The iterator is empty if there are
+    /// none. Decoding is performed as in [`Absolute::query_value()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let uri = uri!("https://rocket.rs/search?tag=web&q=rocket&tag=rust%20lang");
+    /// let tags: Vec<_> = uri.query_values("tag").collect();
+    /// assert_eq!(tags, &["web", "rust lang"]);
+    ///
+    /// assert_eq!(uri.query_values("q").count(), 1);
+    /// assert_eq!(uri.query_values("lang").count(), 0);
+    /// ```
+    pub fn query_values<'s>(&'s self, key: &'s str) -> impl Iterator<Item = &'s str> + 's {
+        self.query()
+            .into_iter()
+            .flat_map(|query| query.segments())
+            .filter(move |(name, _)| *name == key)
+            .map(|(_, value)| value)
+    }
+
     /// Removes the query part of this URI, if there is any.
     ///
     /// # Example
@@ -388,6 +487,44 @@ impl<'a> Absolute<'a> {
         self.set_authority(authority);
         self
     }
+
+    /// Creates an absolute URI from its parts: the scheme `scheme`, the
+    /// authority `authority`, if any, and the path and query of `origin`.
+    /// Returns an `Error` if `scheme` is not a valid URI scheme.
+    ///
+    /// As `origin`'s path is always absolute, the resulting URI is valid even
+    /// with an authority. The parts are moved, not reparsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::uri::Absolute;
+    ///
+    /// let uri = Absolute::try_from_parts("https", uri!("rocket.rs"), uri!("/a/b?c")).unwrap();
+    /// assert_eq!(uri, uri!("https://rocket.rs/a/b?c"));
+    ///
+    /// let uri = Absolute::try_from_parts("file", None, uri!("/etc/hosts")).unwrap();
+    /// assert_eq!(uri, uri!("file:/etc/hosts"));
+    ///
+    /// assert!(Absolute::try_from_parts("no scheme", None, uri!("/")).is_err());
+    /// ```
+    pub fn try_from_parts<A>(
+        scheme: &'a str,
+        authority: A,
+        origin: Origin<'a>,
+    ) -> Result<Absolute<'a>, Error<'a>>
+        where A: Into<Option<Authority<'a>>>
+    {
+        let scheme = crate::parse::uri::scheme_from_str(scheme)?;
+        Ok(Absolute {
+            source: origin.source,
+            scheme: IndexedStr::Concrete(Cow::Borrowed(scheme)),
+            authority: authority.into(),
+            path: origin.path,
+            query: origin.query,
+        })
+    }
 }
 
 /// PRIVATE API.
@@ -484,3 +621,85 @@ impl std::fmt::Display for Absolute<'_> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Absolute;
+    use crate::uri::{Authority, Origin};
+
+    fn values<'a>(uri: &'a Absolute<'a>, key: &'a str) -> Vec<&'a str> {
+        uri.query_values(key).collect()
+    }
+
+    #[test]
+    fn query_value_present() {
+        let uri = Absolute::parse("http://rocket.rs/?a=1&b=two&c").unwrap();
+        assert_eq!(uri.query_value("a"), Some("1"));
+        assert_eq!(uri.query_value("b"), Some("two"));
+        assert_eq!(uri.query_value("c"), Some(""));
+
+        let uri = Absolute::parse("http://rocket.rs/?a+b%2F=some+one%40gmail.com").unwrap();
+        assert_eq!(uri.query_value("a b/"), Some("some one@gmail.com"));
+        assert_eq!(uri.query_value("a+b%2F"), None);
+
+        let uri = Absolute::parse("http://rocket.rs/?x=a=b").unwrap();
+        assert_eq!(uri.query_value("x"), Some("a=b"));
+    }
+
+    #[test]
+    fn query_value_absent() {
+        let uri = Absolute::parse("http://rocket.rs/?a=1&&b").unwrap();
+        assert_eq!(uri.query_value("c"), None);
+        assert_eq!(uri.query_value(""), None);
+        assert!(values(&uri, "c").is_empty());
+
+        let uri = Absolute::parse("http://rocket.rs/").unwrap();
+        assert_eq!(uri.query_value("a"), None);
+        assert!(values(&uri, "a").is_empty());
+
+        let uri = Absolute::parse("http://rocket.rs/?").unwrap();
+        assert_eq!(uri.query_value("a"), None);
+    }
+
+    #[test]
+    fn from_parts() {
+        let authority = Authority::parse("user@rocket.rs:8000").unwrap();
+        let origin = Origin::parse("/a/b?c=d").unwrap();
+        let uri = Absolute::try_from_parts("https", authority, origin).unwrap();
+        assert_eq!(uri.scheme(), "https");
+        assert_eq!(uri.authority().unwrap().user_info(), Some("user"));
+        assert_eq!(uri.authority().unwrap().port(), Some(8000));
+        assert_eq!(uri.path(), "/a/b");
+        assert_eq!(uri.query().unwrap(), "c=d");
+        assert_eq!(uri.to_string(), "https://user@rocket.rs:8000/a/b?c=d");
+
+        let origin = Origin::parse_owned("/x/y".to_string()).unwrap();
+        let uri = Absolute::try_from_parts("ftp", None, origin).unwrap();
+        assert!(uri.authority().is_none());
+        assert_eq!(uri.to_string(), "ftp:/x/y");
+
+        for bad in &["", "1http", "ht tp", "http:"] {
+            let result = Absolute::try_from_parts(bad, None, Origin::ROOT);
+            assert!(result.is_err(), "{:?} is a valid scheme", bad);
+        }
+    }
+
+    #[test]
+    fn authority_with_scheme() {
+        let uri = Authority::parse("rocket.rs:443").unwrap().with_scheme("https").unwrap();
+        assert_eq!(uri.scheme(), "https");
+        assert_eq!(uri.path(), "");
+        assert!(uri.is_normalized());
+        assert_eq!(uri.to_string(), "https://rocket.rs:443");
+
+        assert!(Authority::parse("rocket.rs").unwrap().with_scheme("h:").is_none());
+    }
+
+    #[test]
+    fn query_value_repeated() {
+        let uri = Absolute::parse("http://rocket.rs/?a=1&b=2&a=3&a&a=4%205").unwrap();
+        assert_eq!(uri.query_value("a"), Some("1"));
+        assert_eq!(values(&uri, "a"), &["1", "3", "", "4 5"]);
+        assert_eq!(values(&uri, "b"), &["2"]);
+    }
+}
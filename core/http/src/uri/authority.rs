@@ -3,7 +3,7 @@ use std::borrow::Cow;
 
 use crate::ext::IntoOwned;
 use crate::parse::{Extent, IndexedStr};
-use crate::uri::{as_utf8_unchecked, error::Error};
+use crate::uri::{Absolute, as_utf8_unchecked, error::Error};
 
 /// A URI with an authority only: `user:pass@host:8000`.
 ///
@@ -206,6 +206,25 @@ impl<'a> Authority<'a> {
     pub fn port(&self) -> Option<u16> {
         self.port
     }
+
+    /// Returns an absolute URI with scheme `scheme`, authority `self`, and an
+    /// empty path. Returns `None` if `scheme` is not a valid URI scheme.
+    ///
+    /// To include a path and query, use [`Absolute::try_from_parts()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// let uri = uri!("rocket.rs:8000").with_scheme("http").unwrap();
+    /// assert_eq!(uri, uri!("http://rocket.rs:8000"));
+    ///
+    /// assert!(uri!("rocket.rs").with_scheme("http://").is_none());
+    /// ```
+    pub fn with_scheme(self, scheme: &'a str) -> Option<Absolute<'a>> {
+        let scheme = crate::parse::uri::scheme_from_str(scheme).ok()?;
+        Some(Absolute::const_new(scheme, Some(self), "", None))
+    }
 }
 
 impl_serde!(Authority<'a>, "an authority-form URI");
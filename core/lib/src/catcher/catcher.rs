@@ -3,7 +3,7 @@ use std::io::Cursor;
 
 use crate::response::Response;
 use crate::request::Request;
-use crate::http::{Status, ContentType, uri};
+use crate::http::{Status, ContentType, MediaType, uri};
 use crate::catcher::{Handler, BoxFuture};
 
 use yansi::Paint;
@@ -52,10 +52,31 @@ use yansi::Paint;
 /// ### Built-In Default
 ///
 /// Rocket's provides a built-in default catcher that can handle all errors. It
-/// produces HTML or JSON, depending on the value of the `Accept` header. As
-/// such, catchers only need to be registered if an error needs to be handled in
-/// a custom fashion. The built-in default never conflicts with any
-/// user-registered catchers.
+/// produces HTML, JSON, or plain text, negotiated against the `Accept` header
+/// via [`Accept::negotiate()`], preferring HTML. As such, catchers only need to
+/// be registered if an error needs to be handled in a custom fashion. The
+/// built-in default never conflicts with any user-registered catchers.
+///
+/// Custom catchers can negotiate in the same way:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::http::{ContentType, MediaType, Status};
+///
+/// #[catch(default)]
+/// fn default_catcher(status: Status, req: &Request) -> (ContentType, String) {
+///     let offered = [MediaType::JSON, MediaType::Plain];
+///     match req.accept().map_or(offered.first(), |a| a.negotiate(&offered)) {
+///         Some(media_type) if media_type.is_json() => {
+///             (ContentType::JSON, format!("{{\"code\": {}}}", status.code))
+///         }
+///         _ => (ContentType::Plain, status.to_string()),
+///     }
+/// }
+/// ```
+///
+/// [`Accept::negotiate()`]: crate::http::Accept::negotiate()
 ///
 /// # Code Generation
 ///
@@ -313,6 +334,12 @@ r#"{
     )
 }
 
+macro_rules! text_error_template {
+    ($code:expr, $reason:expr, $description:expr) => (
+        concat!($code, ": ", $reason, "\n", $description, "\n")
+    )
+}
+
 // This is unfortunate, but the `{`, `}` above make it unusable for `format!`.
 macro_rules! json_error_fmt_template {
     ($code:expr, $reason:expr, $description:expr) => (
@@ -336,8 +363,15 @@ macro_rules! default_handler_fn {
             status: Status,
             req: &'r Request<'_>
         ) -> Response<'r> {
-            let preferred = req.accept().map(|a| a.preferred());
-            let (mime, text) = if preferred.map_or(false, |a| a.is_json()) {
+            static OFFERED: [MediaType; 3] = [MediaType::HTML, MediaType::JSON, MediaType::Plain];
+
+            // Without an `Accept` header, or if nothing offered is acceptable,
+            // fall back to the first offered media type: HTML.
+            let negotiated = req.accept()
+                .and_then(|accept| accept.negotiate(&OFFERED))
+                .unwrap_or(&OFFERED[0]);
+
+            let (mime, text) = if negotiated.is_json() {
                 let json: Cow<'_, str> = match status.code {
                     $($code => json_error_template!($code, $reason, $description).into(),)*
                     code => format!(json_error_fmt_template!("{}", "Unknown Error",
@@ -345,6 +379,14 @@ macro_rules! default_handler_fn {
                 };
 
                 (ContentType::JSON, json)
+            } else if negotiated.is_plain() {
+                let text: Cow<'_, str> = match status.code {
+                    $($code => text_error_template!($code, $reason, $description).into(),)*
+                    code => format!(text_error_template!("{}", "Unknown Error",
+                            "An unknown error has occurred."), code).into()
+                };
+
+                (ContentType::Plain, text)
             } else {
                 let html: Cow<'_, str> = match status.code {
                     $($code => html_error_template!($code, $reason, $description).into(),)*
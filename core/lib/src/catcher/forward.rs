@@ -0,0 +1,55 @@
+use crate::request::Request;
+use crate::response::{self, Responder};
+use crate::http::Status;
+
+/// A catcher responder that hands the error off to the catcher for another
+/// status.
+///
+/// Returning `Forward(status)` from a catcher stops the current catcher and
+/// invokes the catcher for `status` instead, exactly as if the error had
+/// originally been `status`. This allows error handling logic to be chained:
+/// a `404` catcher might decide that the request is actually unauthorized and
+/// forward to the `401` catcher, for instance.
+///
+/// Forwards are bounded: a catcher may not forward to a status whose catcher
+/// has already been invoked for the request. Such a forward, like any other
+/// catcher failure, results in the `500` catcher being invoked. Returning
+/// `Forward` from anywhere other than a catcher is equivalent to returning
+/// `Err(status)`.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+/// use rocket::catcher::Forward;
+/// use rocket::http::Status;
+///
+/// #[catch(404)]
+/// fn not_found(req: &Request) -> Result<String, Forward> {
+///     if req.uri().path().starts_with("/admin") {
+///         return Err(Forward(Status::Unauthorized));
+///     }
+///
+///     Ok(format!("Sorry, '{}' is not a valid path.", req.uri()))
+/// }
+///
+/// #[catch(401)]
+/// fn unauthorized() -> &'static str {
+///     "Please log in."
+/// }
+///
+/// #[launch]
+/// fn rocket() -> _ {
+///     rocket::build().register("/", catchers![not_found, unauthorized])
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Forward(pub Status);
+
+impl<'r> Responder<'r, 'static> for Forward {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        req.set_catcher_forward(self.0);
+        Err(self.0)
+    }
+}
@@ -2,6 +2,8 @@
 
 mod catcher;
 mod handler;
+mod forward;
 
 pub use catcher::*;
 pub use handler::*;
+pub use forward::Forward;
@@ -6,9 +6,11 @@ use figment::value::{Map, Dict, magic::RelativePathBuf};
 use serde::{Deserialize, Serialize};
 use yansi::Paint;
 
-use crate::config::{LogLevel, Shutdown, Ident};
+use crate::config::{LogLevel, Shutdown, Ident, TrailingSlash};
 use crate::request::{self, Request, FromRequest};
-use crate::data::Limits;
+use crate::data::{Limits, ByteUnit, ToByteUnit};
+use crate::fs::ContentTypes;
+use crate::http::uri::Absolute;
 
 #[cfg(feature = "tls")]
 use crate::config::TlsConfig;
@@ -31,7 +33,8 @@ use crate::config::SecretKey;
 /// the release profile. The [`Config::default()`] method automatically selects
 /// the appropriate of the two based on the selected profile. With the exception
 /// of `log_level`, which is `normal` in `debug` and `critical` in `release`,
-/// and `secret_key`, which is regenerated from a random value if not set in
+/// `trace_forwards`, which is `true` in `debug` and `false` in `release`, and
+/// `secret_key`, which is regenerated from a random value if not set in
 /// "debug" mode only, all default values are identical in all profiles.
 ///
 /// # Provider Details
@@ -75,17 +78,134 @@ pub struct Config {
     pub workers: usize,
     /// Limit on threads to start for blocking tasks. **(default: `512`)**
     pub max_blocking: usize,
+    /// Prefix of the names of the threads of Rocket's async runtime.
+    /// **(default: `"rocket-worker"`)**
+    ///
+    /// Threads are named `{prefix}-thread`. Rocket checks that it is running
+    /// on such a thread to determine whether it is executing inside its own
+    /// runtime, and thus whether it can force the runtime to shut down.
+    ///
+    /// _**Note:** Rocket only reads this value from sources in the [default
+    /// provider](Config::figment())._
+    pub worker_name_prefix: String,
     /// How, if at all, to identify the server via the `Server` header.
     /// **(default: `"Rocket"`)**
     pub ident: Ident,
     /// Streaming read size limits. **(default: [`Limits::default()`])**
     pub limits: Limits,
+    /// Content types of files served by [`NamedFile`](crate::fs::NamedFile),
+    /// by extension, in addition to and overriding the built-in ones.
+    /// **(default: [`ContentTypes::default()`])**
+    pub content_types: ContentTypes,
     /// Directory to store temporary files in. **(default:
     /// [`std::env::temp_dir()`])**
     #[serde(serialize_with = "RelativePathBuf::serialize_relative")]
     pub temp_dir: RelativePathBuf,
     /// Keep-alive timeout in seconds; disabled when `0`. **(default: `5`)**
     pub keep_alive: u32,
+    /// Idle connection timeout in seconds; disabled when `0`. **(default:
+    /// `0`)**
+    ///
+    /// A connection without any I/O activity and without any request being
+    /// processed for this long is closed. Unlike `keep_alive`, which governs
+    /// whether and how long the HTTP layer keeps a connection open between
+    /// requests, this applies to all connections regardless of protocol state,
+    /// including those that never send a complete request.
+    pub idle_timeout: u32,
+    /// Whether to honor the `X-HTTP-Method-Override` header on `POST` requests.
+    /// **(default: `false`)**
+    ///
+    /// When enabled, the method of a `POST` request carrying a valid
+    /// `X-HTTP-Method-Override` header is rewritten to the method named in the
+    /// header before routing. A `_method` form field, if present, takes
+    /// precedence over the header.
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub header_method_override: bool,
+    /// Maximum number of headers in a request. **(default: `100`)**
+    ///
+    /// Requests with more headers are rejected with a `431 Request Header
+    /// Fields Too Large`. The HTTP/1 server never accepts more than `100`
+    /// headers, so a larger value is reduced to `100`, with a warning, when
+    /// Rocket ignites.
+    pub max_headers: usize,
+    /// Maximum combined size of the names and values of the headers in a
+    /// request. **(default: `64KiB`)**
+    ///
+    /// Requests with larger headers are rejected with a `431 Request Header
+    /// Fields Too Large`. The HTTP/1 server additionally buffers no more than
+    /// this amount, but at least `8KiB`, while reading the request head.
+    pub max_header_size: ByteUnit,
+    /// Maximum length, in bytes, of the path and query of a request's target.
+    /// **(default: `8192`)**
+    ///
+    /// Requests with longer targets are rejected with a `414 URI Too Long`
+    /// before the target is parsed.
+    pub max_uri_length: usize,
+    /// Maximum number of requests to process concurrently; unlimited when `0`.
+    /// **(default: `0`)**
+    ///
+    /// Requests beyond the limit are not routed. Instead, they are responded
+    /// to with a `503 Service Unavailable` carrying a `Connection: close`
+    /// header, so that the client does not reuse the connection, and a
+    /// `Retry-After` header as configured by [`Config::retry_after`].
+    pub max_concurrent_requests: usize,
+    /// Number of seconds clients are asked to wait before retrying after a
+    /// `503 Service Unavailable` that Rocket generates, such as when shedding
+    /// load; no `Retry-After` header is sent when `0`. **(default: `5`)**
+    pub retry_after: u32,
+    /// Maximum number of request body bytes in flight across all requests;
+    /// unlimited when `0`. **(default: `0`)**
+    ///
+    /// Unlike [`Config::limits`], which bound each request, this is a global
+    /// budget. Opening a body reserves the lesser of its read limit and its
+    /// declared length from the budget, and the first read waits until the
+    /// reservation fits. The reservation is released when the body's stream
+    /// is dropped. Reservations larger than the budget are capped at the
+    /// whole budget, and the budget is accounted in units of `1KiB`.
+    pub max_total_body_bytes: ByteUnit,
+    /// How routing treats a trailing slash in a request's path.
+    /// **(default: [`TrailingSlash::Ignore`])**
+    ///
+    /// See [`TrailingSlash`] for the available policies.
+    pub trailing_slash: TrailingSlash,
+    /// Whether to record why each route tried for a request forwarded.
+    /// **(default: _debug_ `true` / _release_ `false`)**
+    ///
+    /// When enabled, the routes a request was routed to but that forwarded,
+    /// along with the reason for each, are available via
+    /// [`Request::forwards()`](crate::Request::forwards()), for example from a
+    /// `404` catcher. When disabled, nothing is recorded.
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub trace_forwards: bool,
+    /// Whether to send a `Server-Timing` header with each response.
+    /// **(default: `false`)**
+    ///
+    /// When enabled, the timings recorded for a request via
+    /// [`Request::record_timing()`](crate::Request::record_timing()), along
+    /// with the `total` time spent handling the request, are sent in a
+    /// [`Server-Timing`] header, making them visible in browser developer
+    /// tools. As this exposes server internals, it should only be enabled
+    /// during development.
+    ///
+    /// [`Server-Timing`]: https://www.w3.org/TR/server-timing/
+    #[serde(deserialize_with = "figment::util::bool_from_str_or_int")]
+    pub server_timing: bool,
+    /// The externally visible base URL of the application, if any.
+    /// **(default: `None`)**
+    ///
+    /// When set, [`Request::url_for()`](crate::Request::url_for()) builds
+    /// absolute URLs with this URL's scheme and authority, and with its path,
+    /// if any, as a prefix, ignoring the request's `Host`. This is the URL
+    /// clients see, which often differs from the bound address when a proxy,
+    /// such as a TLS-terminating one, sits in front of the application.
+    pub public_url: Option<Absolute<'static>>,
+    /// IP addresses of proxies trusted to set the `X-Forwarded-Proto` and
+    /// `X-Forwarded-Host` headers. **(default: `[]`)**
+    ///
+    /// These headers are honored by
+    /// [`Request::url_for()`](crate::Request::url_for()) only when the
+    /// request's remote address is in this list and `public_url` is not set.
+    pub trusted_proxies: Vec<IpAddr>,
     /// The TLS configuration, if any. **(default: `None`)**
     #[cfg(feature = "tls")]
     #[cfg_attr(nightly, doc(cfg(feature = "tls")))]
@@ -151,6 +271,9 @@ impl Config {
         ("dev", Some("debug")), ("prod", Some("release")), ("stag", None)
     ];
 
+    /// The most headers the HTTP/1 server accepts in a request.
+    pub(crate) const MAX_HEADERS_CAP: usize = 100;
+
     /// Returns the default configuration for the `debug` profile, _irrespective
     /// of the Rust compilation profile_ and `ROCKET_PROFILE`.
     ///
@@ -173,10 +296,25 @@ impl Config {
             port: 8000,
             workers: num_cpus::get(),
             max_blocking: 512,
+            worker_name_prefix: "rocket-worker".into(),
             ident: Ident::default(),
             limits: Limits::default(),
+            content_types: ContentTypes::default(),
             temp_dir: std::env::temp_dir().into(),
             keep_alive: 5,
+            idle_timeout: 0,
+            header_method_override: false,
+            max_headers: 100,
+            max_header_size: 64.kibibytes(),
+            max_uri_length: 8192,
+            max_concurrent_requests: 0,
+            retry_after: 5,
+            max_total_body_bytes: ByteUnit::from(0),
+            trailing_slash: TrailingSlash::Ignore,
+            trace_forwards: true,
+            server_timing: false,
+            public_url: None,
+            trusted_proxies: vec![],
             #[cfg(feature = "tls")]
             tls: None,
             #[cfg(feature = "secrets")]
@@ -207,6 +345,7 @@ impl Config {
         Config {
             profile: Self::RELEASE_PROFILE,
             log_level: LogLevel::Critical,
+            trace_forwards: false,
             ..Config::debug_default()
         }
     }
@@ -350,6 +489,14 @@ impl Config {
         #[cfg(not(feature = "mtls"))] { false }
     }
 
+    /// Returns `true` if the current thread's name starts with
+    /// [`Config::worker_name_prefix`], indicating it belongs to Rocket's
+    /// runtime.
+    pub(crate) fn in_worker_thread(&self) -> bool {
+        std::thread::current().name()
+            .map_or(false, |name| name.starts_with(&*self.worker_name_prefix))
+    }
+
     pub(crate) fn pretty_print(&self, figment: &Figment) {
         use crate::log::PaintExt;
 
@@ -362,8 +509,10 @@ impl Config {
         launch_info_!("port: {}", bold(&self.port));
         launch_info_!("workers: {}", bold(self.workers));
         launch_info_!("max blocking threads: {}", bold(self.max_blocking));
+        launch_info_!("worker name prefix: {}", bold(&self.worker_name_prefix));
         launch_info_!("ident: {}", bold(&self.ident));
         launch_info_!("limits: {}", bold(&self.limits));
+        launch_info_!("content types: {}", bold(&self.content_types));
         launch_info_!("temp dir: {}", bold(&self.temp_dir.relative().display()));
         launch_info_!("http/2: {}", bold(cfg!(feature = "http2")));
 
@@ -372,6 +521,46 @@ impl Config {
             ka => launch_info_!("keep-alive: {}{}", bold(ka), bold("s")),
         }
 
+        match self.idle_timeout {
+            0 => launch_info_!("idle timeout: {}", bold("disabled")),
+            t => launch_info_!("idle timeout: {}{}", bold(t), bold("s")),
+        }
+
+        launch_info_!("method override header: {}", bold(self.header_method_override));
+        launch_info_!("max headers: {}", bold(self.max_headers));
+        launch_info_!("max header size: {}", bold(self.max_header_size));
+        launch_info_!("max uri length: {}", bold(self.max_uri_length));
+        match self.max_concurrent_requests {
+            0 => launch_info_!("max concurrent requests: {}", bold("unlimited")),
+            max => launch_info_!("max concurrent requests: {}", bold(max)),
+        }
+
+        match self.retry_after {
+            0 => launch_info_!("retry after: {}", bold("disabled")),
+            s => launch_info_!("retry after: {}{}", bold(s), bold("s")),
+        }
+
+        match self.max_total_body_bytes.as_u64() {
+            0 => launch_info_!("max total body bytes: {}", bold("unlimited")),
+            _ => launch_info_!("max total body bytes: {}", bold(self.max_total_body_bytes)),
+        }
+
+        launch_info_!("trailing slash: {}", bold(self.trailing_slash));
+        launch_info_!("trace forwards: {}", bold(self.trace_forwards));
+        launch_info_!("server timing: {}", bold(self.server_timing));
+        match &self.public_url {
+            Some(url) => launch_info_!("public url: {}", bold(url)),
+            None => launch_info_!("public url: {}", bold("none")),
+        }
+
+        match self.trusted_proxies.as_slice() {
+            [] => launch_info_!("trusted proxies: {}", bold("none")),
+            proxies => {
+                let proxies: Vec<_> = proxies.iter().map(|ip| ip.to_string()).collect();
+                launch_info_!("trusted proxies: {}", bold(proxies.join(", ")));
+            }
+        }
+
         match (self.tls_enabled(), self.mtls_enabled()) {
             (true, true) => launch_info_!("tls: {}", bold("enabled w/mtls")),
             (true, false) => launch_info_!("tls: {} w/o mtls", bold("enabled")),
@@ -458,12 +647,70 @@ impl Config {
     /// The stringy parameter name for setting/extracting [`Config::max_blocking`].
     pub const MAX_BLOCKING: &'static str = "max_blocking";
 
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::worker_name_prefix`].
+    pub const WORKER_NAME_PREFIX: &'static str = "worker_name_prefix";
+
     /// The stringy parameter name for setting/extracting [`Config::keep_alive`].
     pub const KEEP_ALIVE: &'static str = "keep_alive";
 
+    /// The stringy parameter name for setting/extracting [`Config::idle_timeout`].
+    pub const IDLE_TIMEOUT: &'static str = "idle_timeout";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::header_method_override`].
+    pub const HEADER_METHOD_OVERRIDE: &'static str = "header_method_override";
+
+    /// The stringy parameter name for setting/extracting [`Config::max_headers`].
+    pub const MAX_HEADERS: &'static str = "max_headers";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::max_header_size`].
+    pub const MAX_HEADER_SIZE: &'static str = "max_header_size";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::max_uri_length`].
+    pub const MAX_URI_LENGTH: &'static str = "max_uri_length";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::max_concurrent_requests`].
+    pub const MAX_CONCURRENT_REQUESTS: &'static str = "max_concurrent_requests";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::retry_after`].
+    pub const RETRY_AFTER: &'static str = "retry_after";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::max_total_body_bytes`].
+    pub const MAX_TOTAL_BODY_BYTES: &'static str = "max_total_body_bytes";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::trailing_slash`].
+    pub const TRAILING_SLASH: &'static str = "trailing_slash";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::trace_forwards`].
+    pub const TRACE_FORWARDS: &'static str = "trace_forwards";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::server_timing`].
+    pub const SERVER_TIMING: &'static str = "server_timing";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::public_url`].
+    pub const PUBLIC_URL: &'static str = "public_url";
+
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::trusted_proxies`].
+    pub const TRUSTED_PROXIES: &'static str = "trusted_proxies";
+
     /// The stringy parameter name for setting/extracting [`Config::limits`].
     pub const LIMITS: &'static str = "limits";
 
+    /// The stringy parameter name for setting/extracting
+    /// [`Config::content_types`].
+    pub const CONTENT_TYPES: &'static str = "content_types";
+
     /// The stringy parameter name for setting/extracting [`Config::tls`].
     pub const TLS: &'static str = "tls";
 
@@ -114,6 +114,7 @@
 mod ident;
 mod config;
 mod shutdown;
+mod trailing_slash;
 
 #[cfg(feature = "tls")]
 mod tls;
@@ -127,6 +128,7 @@ pub use config::Config;
 pub use crate::log::LogLevel;
 pub use shutdown::Shutdown;
 pub use ident::Ident;
+pub use trailing_slash::TrailingSlash;
 
 #[cfg(feature = "tls")]
 pub use tls::{TlsConfig, CipherSuite};
@@ -142,13 +144,16 @@ pub use shutdown::Sig;
 
 #[cfg(test)]
 mod tests {
-    use std::net::Ipv4Addr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
     use figment::{Figment, Profile};
     use pretty_assertions::assert_eq;
 
     use crate::log::LogLevel;
     use crate::data::{Limits, ToByteUnit};
-    use crate::config::Config;
+    use crate::config::{Config, TrailingSlash};
+    use crate::fs::ContentTypes;
+    use crate::http::ContentType;
+    use crate::http::uri::Absolute;
 
     #[test]
     fn test_figment_is_default() {
@@ -202,7 +207,21 @@ mod tests {
                 ident = "Something Cool"
                 port = 1234
                 workers = 20
+                worker_name_prefix = "my-app"
                 keep_alive = 10
+                idle_timeout = 30
+                max_headers = 50
+                max_header_size = "16KiB"
+                max_uri_length = 2048
+                max_concurrent_requests = 256
+                retry_after = 30
+                max_total_body_bytes = "16MiB"
+                trailing_slash = "redirect"
+                trace_forwards = false
+                server_timing = true
+                public_url = "https://rocket.rs/app"
+                trusted_proxies = ["10.0.0.1", "::1"]
+                content_types = { webmanifest = "application/manifest+json" }
                 log_level = "off"
                 cli_colors = 0
             "#)?;
@@ -212,8 +231,23 @@ mod tests {
                 address: Ipv4Addr::new(1, 2, 3, 4).into(),
                 port: 1234,
                 workers: 20,
+                worker_name_prefix: "my-app".into(),
                 ident: ident!("Something Cool"),
                 keep_alive: 10,
+                idle_timeout: 30,
+                max_headers: 50,
+                max_header_size: 16.kibibytes(),
+                max_uri_length: 2048,
+                max_concurrent_requests: 256,
+                retry_after: 30,
+                max_total_body_bytes: 16.mebibytes(),
+                trailing_slash: TrailingSlash::Redirect,
+                trace_forwards: false,
+                server_timing: true,
+                public_url: Absolute::parse("https://rocket.rs/app").ok(),
+                trusted_proxies: vec![Ipv4Addr::new(10, 0, 0, 1).into(), Ipv6Addr::LOCALHOST.into()],
+                content_types: ContentTypes::new()
+                    .content_type("webmanifest", ContentType::new("application", "manifest+json")),
                 log_level: LogLevel::Off,
                 cli_colors: false,
                 ..Config::default()
@@ -268,6 +302,33 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_worker_name_prefix() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("ROCKET_WORKERS", 3);
+            jail.set_env("ROCKET_WORKER_NAME_PREFIX", "my-app");
+
+            let config = Config::from(Config::figment());
+            assert_eq!(config.workers, 3);
+            assert!(!config.in_worker_thread());
+
+            // `block_on` runs on the current thread: spawn to run on a worker.
+            let result = crate::async_main(async move {
+                tokio::spawn(async move {
+                    assert!(config.in_worker_thread());
+                    assert!(!Config::default().in_worker_thread());
+
+                    let metrics = tokio::runtime::Handle::current().metrics();
+                    assert_eq!(metrics.num_workers(), 3);
+                }).await
+            });
+
+            result.unwrap();
+
+            Ok(())
+        });
+    }
+
     #[test]
     #[cfg(feature = "tls")]
     fn test_tls_config_from_file() {
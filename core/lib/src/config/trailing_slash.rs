@@ -0,0 +1,56 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// How routing treats a trailing slash in a request's path.
+///
+/// Route paths never end in a slash: `/foo/` is normalized to `/foo` when a
+/// route is created. This policy determines whether a request for `/foo/`
+/// matches such a route. In all cases, a route whose path ends in a trailing
+/// parameter, like `/foo/<path..>`, matches requests with or without a
+/// trailing slash, and a request for `/` is unaffected.
+///
+/// ## (De)serialization
+///
+/// A `TrailingSlash` variant serializes and deserializes as a lowercase string
+/// equal to the name of the variant: `"ignore"`, `"strict"`, or `"redirect"`.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::{Config, TrailingSlash};
+///
+/// let config = Config {
+///     trailing_slash: TrailingSlash::Redirect,
+///     ..Config::default()
+/// };
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrailingSlash {
+    /// A trailing slash is ignored: `/foo/` matches `/foo`. This is the
+    /// default.
+    Ignore,
+    /// A trailing slash is significant: `/foo/` doesn't match `/foo`.
+    Strict,
+    /// As with [`TrailingSlash::Strict`], but a request that matches no route
+    /// only because of its trailing slash is permanently redirected (`308`)
+    /// to the path without it, query intact: `/foo/?a` redirects to `/foo?a`.
+    Redirect,
+}
+
+impl Default for TrailingSlash {
+    fn default() -> Self {
+        TrailingSlash::Ignore
+    }
+}
+
+impl fmt::Display for TrailingSlash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrailingSlash::Ignore => "ignore".fmt(f),
+            TrailingSlash::Strict => "strict".fmt(f),
+            TrailingSlash::Redirect => "redirect".fmt(f),
+        }
+    }
+}
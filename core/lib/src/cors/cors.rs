@@ -0,0 +1,345 @@
+use std::sync::Arc;
+
+use time::Duration;
+
+use crate::{Rocket, Build, Request, Response, Data, Route};
+use crate::fairing::{self, Fairing, Info, Kind};
+use crate::route::{Handler, Outcome};
+use crate::http::{Method, Status, uncased::Uncased};
+
+/// A [`Fairing`] that implements Cross-Origin Resource Sharing (CORS).
+///
+/// See the [module level docs](crate::cors) for an overview of how requests
+/// are handled.
+///
+/// # Usage
+///
+/// A `Cors` created with [`Cors::new()`] allows no origins. Allow origins with
+/// [`allow_origin()`](Cors::allow_origin()) or
+/// [`allow_any_origin()`](Cors::allow_any_origin()) and configure the rest of
+/// the policy with the remaining chainable methods:
+///
+/// ```rust
+/// use rocket::cors::Cors;
+/// use rocket::http::Method;
+/// use rocket::time::Duration;
+///
+/// let cors = Cors::new()
+///     .allow_origin("https://rocket.rs")
+///     .allow_methods([Method::Get, Method::Put])
+///     .allow_headers(["Content-Type"])
+///     .max_age(Duration::hours(1));
+/// ```
+///
+/// Then, attach the instance of `Cors` to your application's instance of
+/// `Rocket`:
+///
+/// ```rust
+/// # extern crate rocket;
+/// # use rocket::cors::Cors;
+/// # let cors = Cors::new();
+/// rocket::build()
+///     // ...
+///     .attach(cors)
+/// # ;
+/// ```
+///
+/// Only one `Cors` can be attached; attaching another replaces the first.
+#[derive(Debug, Clone)]
+pub struct Cors {
+    /// The allowed origins, lowercased, or `None` if any origin is allowed.
+    origins: Option<Vec<String>>,
+    methods: Vec<Method>,
+    headers: Vec<Uncased<'static>>,
+    credentials: bool,
+    max_age: Option<Duration>,
+}
+
+impl Default for Cors {
+    /// Returns a new `Cors` instance. Equivalent to [`Cors::new()`].
+    fn default() -> Self {
+        Cors::new()
+    }
+}
+
+impl Cors {
+    /// The rank of the preflight route: lower in priority than any other.
+    const RANK: isize = isize::MAX;
+
+    /// Returns a `Cors` that allows no origins, the methods `GET`, `HEAD`, and
+    /// `POST`, no request headers, and no credentials, and that doesn't set a
+    /// preflight `Access-Control-Max-Age`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new();
+    /// ```
+    pub fn new() -> Self {
+        Cors {
+            origins: Some(vec![]),
+            methods: vec![Method::Get, Method::Head, Method::Post],
+            headers: vec![],
+            credentials: false,
+            max_age: None,
+        }
+    }
+
+    /// Allows requests from `origin`, a serialized origin such as
+    /// `https://rocket.rs` or `http://localhost:8000`.
+    ///
+    /// The origin may contain a single `*`, which matches one or more
+    /// alphanumerics, `-`, and `.`: `https://*.rocket.rs` allows all subdomains
+    /// of `rocket.rs` over HTTPS, but not `https://rocket.rs` itself. Origins
+    /// are compared case-insensitively. This method has no effect after
+    /// [`Cors::allow_any_origin()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new()
+    ///     .allow_origin("https://rocket.rs")
+    ///     .allow_origin("https://*.rocket.rs");
+    /// ```
+    pub fn allow_origin<S: AsRef<str>>(mut self, origin: S) -> Self {
+        if let Some(ref mut origins) = self.origins {
+            origins.push(origin.as_ref().to_ascii_lowercase());
+        }
+
+        self
+    }
+
+    /// Allows requests from any origin.
+    ///
+    /// Responses carry `Access-Control-Allow-Origin: *` unless credentials
+    /// are allowed, in which case the request's origin is echoed instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new().allow_any_origin();
+    /// ```
+    pub fn allow_any_origin(mut self) -> Self {
+        self.origins = None;
+        self
+    }
+
+    /// Sets the methods that preflight requests may request to `methods`,
+    /// replacing any previously set. **(default: `GET`, `HEAD`, `POST`)**
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    /// use rocket::http::Method;
+    ///
+    /// let cors = Cors::new().allow_methods([Method::Get, Method::Delete]);
+    /// ```
+    pub fn allow_methods<I: IntoIterator<Item = Method>>(mut self, methods: I) -> Self {
+        self.methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Sets the request headers that preflight requests may request to
+    /// `headers`, replacing any previously set. Header names are compared
+    /// case-insensitively. **(default: none)**
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new().allow_headers(["Content-Type", "X-Api-Key"]);
+    /// ```
+    pub fn allow_headers<I, S>(mut self, headers: I) -> Self
+        where I: IntoIterator<Item = S>, S: Into<String>
+    {
+        self.headers = headers.into_iter().map(|h| Uncased::from(h.into())).collect();
+        self
+    }
+
+    /// Sets whether to allow credentials, such as cookies, with cross-origin
+    /// requests. **(default: `false`)**
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    ///
+    /// let cors = Cors::new()
+    ///     .allow_origin("https://rocket.rs")
+    ///     .allow_credentials(true);
+    /// ```
+    pub fn allow_credentials(mut self, allow: bool) -> Self {
+        self.credentials = allow;
+        self
+    }
+
+    /// Sets how long clients may cache the result of a preflight request.
+    /// Sent as `Access-Control-Max-Age` in whole seconds. **(default: unset)**
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::cors::Cors;
+    /// use rocket::time::Duration;
+    ///
+    /// let cors = Cors::new().max_age(Duration::minutes(10));
+    /// ```
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    fn allows_origin(&self, origin: &str) -> bool {
+        let origin = origin.to_ascii_lowercase();
+        match self.origins {
+            Some(ref origins) => origins.iter().any(|p| origin_matches(p, &origin)),
+            None => true,
+        }
+    }
+
+    fn allows_preflight(&self, req: &Request<'_>) -> bool {
+        let headers = req.headers();
+        let origin_allowed = headers.get_one("Origin")
+            .map_or(false, |origin| self.allows_origin(origin));
+
+        let method_allowed = headers.get_one("Access-Control-Request-Method")
+            .and_then(|method| method.trim().parse().ok())
+            .map_or(false, |method| self.methods.contains(&method));
+
+        let headers_allowed = headers.get("Access-Control-Request-Headers")
+            .flat_map(|value| value.split(','))
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .all(|name| self.headers.iter().any(|h| h == name));
+
+        origin_allowed && method_allowed && headers_allowed
+    }
+}
+
+/// Whether `origin` matches `pattern`. Both must be lowercase.
+fn origin_matches(pattern: &str, origin: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == origin,
+        Some((prefix, suffix)) => {
+            origin.len() > prefix.len() + suffix.len()
+                && origin.starts_with(prefix)
+                && origin.ends_with(suffix)
+                && origin[prefix.len()..(origin.len() - suffix.len())].bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'.')
+        }
+    }
+}
+
+fn is_preflight(req: &Request<'_>) -> bool {
+    req.method() == Method::Options
+        && req.headers().contains("Origin")
+        && req.headers().contains("Access-Control-Request-Method")
+}
+
+/// The handler for the catch-all preflight route.
+#[derive(Clone)]
+struct Preflight(Arc<Cors>);
+
+#[crate::async_trait]
+impl Handler for Preflight {
+    async fn handle<'r>(&self, req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r> {
+        if !is_preflight(req) {
+            return Outcome::forward(data);
+        }
+
+        if !self.0.allows_preflight(req) {
+            warn_!("CORS: refusing disallowed preflight request.");
+            return Outcome::failure(Status::Forbidden);
+        }
+
+        Outcome::from(req, Status::NoContent)
+    }
+}
+
+#[crate::async_trait]
+impl Fairing for Cors {
+    fn info(&self) -> Info {
+        Info {
+            name: "CORS",
+            kind: Kind::Ignite | Kind::Response | Kind::Singleton,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        let preflight = Preflight(Arc::new(self.clone()));
+        let mut route = Route::ranked(Cors::RANK, Method::Options, "/<_..>", preflight);
+        route.name = Some("CORS Preflight".into());
+        route.advertised = false;
+        Ok(rocket.mount("/", vec![route]))
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        // The response depends on the `Origin` unless it's the same for all.
+        if self.origins.is_some() || self.credentials {
+            res.adjoin_raw_header("Vary", "Origin");
+        }
+
+        let origin = match req.headers().get_one("Origin") {
+            Some(origin) if self.allows_origin(origin) => origin,
+            _ => return,
+        };
+
+        if res.headers().contains("Access-Control-Allow-Origin") {
+            warn!("CORS: response contains an 'Access-Control-Allow-Origin' header.");
+            warn_!("Refusing to add CORS headers.");
+            return;
+        }
+
+        if self.origins.is_none() && !self.credentials {
+            res.set_raw_header("Access-Control-Allow-Origin", "*");
+        } else {
+            res.set_raw_header("Access-Control-Allow-Origin", origin.to_string());
+        }
+
+        if self.credentials {
+            res.set_raw_header("Access-Control-Allow-Credentials", "true");
+        }
+
+        if is_preflight(req) {
+            let methods = self.methods.iter().map(|m| m.as_str()).collect::<Vec<_>>();
+            res.set_raw_header("Access-Control-Allow-Methods", methods.join(", "));
+
+            if !self.headers.is_empty() {
+                let headers = self.headers.iter().map(|h| h.as_str()).collect::<Vec<_>>();
+                res.set_raw_header("Access-Control-Allow-Headers", headers.join(", "));
+            }
+
+            if let Some(max_age) = self.max_age {
+                let secs = max_age.whole_seconds().to_string();
+                res.set_raw_header("Access-Control-Max-Age", secs);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::origin_matches;
+
+    #[test]
+    fn test_origin_matches() {
+        assert!(origin_matches("https://rocket.rs", "https://rocket.rs"));
+        assert!(!origin_matches("https://rocket.rs", "http://rocket.rs"));
+        assert!(!origin_matches("https://rocket.rs", "https://rocket.rs:8000"));
+
+        assert!(origin_matches("https://*.rocket.rs", "https://api.rocket.rs"));
+        assert!(origin_matches("https://*.rocket.rs", "https://a.b.rocket.rs"));
+        assert!(!origin_matches("https://*.rocket.rs", "https://rocket.rs"));
+        assert!(!origin_matches("https://*.rocket.rs", "https://.rocket.rs.evil.com"));
+        assert!(!origin_matches("https://*.rocket.rs", "https://evil.com/.rocket.rs"));
+        assert!(origin_matches("http://localhost:*", "http://localhost:8000"));
+    }
+}
@@ -0,0 +1,55 @@
+//! Cross-Origin Resource Sharing (CORS) for all routes.
+//!
+//! The [`Cors`] fairing implements the server side of [CORS]: it answers
+//! preflight requests and adds `Access-Control-*` headers to responses to
+//! cross-origin requests from allowed origins.
+//!
+//! [CORS]: https://developer.mozilla.org/en-US/docs/Web/HTTP/CORS
+//!
+//! # Usage
+//!
+//! Construct a [`Cors`] with the allowed origins and any other options, then
+//! attach it:
+//!
+//! ```rust
+//! # #[macro_use] extern crate rocket;
+//! use rocket::cors::Cors;
+//! use rocket::http::Method;
+//!
+//! #[launch]
+//! fn rocket() -> _ {
+//!     let cors = Cors::new()
+//!         .allow_origin("https://rocket.rs")
+//!         .allow_origin("https://*.rocket.rs")
+//!         .allow_methods([Method::Get, Method::Post, Method::Delete])
+//!         .allow_headers(["Content-Type", "Authorization"])
+//!         .allow_credentials(true);
+//!
+//!     rocket::build().attach(cors)
+//! }
+//! ```
+//!
+//! # Preflight Requests
+//!
+//! Fairings cannot respond to requests directly. Instead, `Cors` mounts a
+//! catch-all `OPTIONS` route, at the lowest priority, which answers
+//! preflight requests: `OPTIONS` requests with `Origin` and
+//! `Access-Control-Request-Method` headers. A preflight thus never fails with
+//! a `404` or `405`, even when no route handles `OPTIONS` for its path.
+//!
+//! A preflight whose origin, requested method, and requested headers are all
+//! allowed is answered with a `204 No Content` carrying the preflight
+//! headers. Any other preflight fails with a `403 Forbidden`. `OPTIONS`
+//! requests that aren't preflights are forwarded.
+//!
+//! # Actual Requests
+//!
+//! Responses to requests carrying an `Origin` header from an allowed origin
+//! receive `Access-Control-Allow-Origin` and, if enabled,
+//! `Access-Control-Allow-Credentials` headers. Responses to requests from
+//! any other origin are left untouched, so that browsers withhold them from
+//! the requesting page.
+
+mod cors;
+
+pub use self::cors::Cors;
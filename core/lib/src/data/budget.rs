@@ -0,0 +1,86 @@
+use std::fmt;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::future::{BoxFuture, FutureExt};
+use tokio::sync::{AcquireError, OwnedSemaphorePermit, Semaphore};
+
+use crate::data::ByteUnit;
+
+/// The granularity, in bytes, of a single budget permit.
+const PERMIT_SIZE: u64 = 1024;
+
+/// The largest number of permits a `tokio` semaphore can hold.
+const MAX_PERMITS: u64 = (usize::MAX >> 3) as u64;
+
+/// Global accounting of in-flight request body bytes.
+///
+/// The budget is shared by every request. Opening a body reserves as much of
+/// the budget as the body may read, as a whole and before any data is read,
+/// and releases the reservation when the stream is dropped. Reserving as a
+/// whole means a reader never holds part of the budget while waiting for the
+/// rest, so readers can't deadlock one another.
+#[derive(Clone)]
+pub(crate) struct BodyBudget {
+    permits: Arc<Semaphore>,
+    total: u32,
+}
+
+/// A reservation against a [`BodyBudget`], acquired on first poll.
+pub(crate) enum Reservation {
+    Pending(BoxFuture<'static, Result<OwnedSemaphorePermit, AcquireError>>),
+    /// The permit, if any, released when dropped.
+    Held { _permit: Option<OwnedSemaphorePermit> },
+}
+
+impl BodyBudget {
+    /// Returns a budget of `limit` bytes or `None` if `limit` is `0`, meaning
+    /// that the budget is unlimited.
+    pub fn new(limit: ByteUnit) -> Option<BodyBudget> {
+        if limit == 0 {
+            return None;
+        }
+
+        let permits = to_permits(limit.as_u64()).min(MAX_PERMITS);
+        let total = permits.min(u32::MAX as u64) as u32;
+        Some(BodyBudget { permits: Arc::new(Semaphore::new(permits as usize)), total })
+    }
+
+    /// Returns a reservation of `bytes` bytes. Reservations larger than the
+    /// entire budget are capped to the entire budget.
+    pub fn reserve(&self, bytes: u64) -> Reservation {
+        let permits = to_permits(bytes).min(self.total as u64) as u32;
+        if permits == 0 {
+            return Reservation::Held { _permit: None };
+        }
+
+        let semaphore = self.permits.clone();
+        Reservation::Pending(Box::pin(semaphore.acquire_many_owned(permits)))
+    }
+}
+
+impl Reservation {
+    /// Polls until the reservation is held.
+    pub fn poll_acquire(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if let Reservation::Pending(ref mut fut) = self {
+            // The semaphore is never closed, so acquisition never fails.
+            let permit = futures::ready!(fut.poll_unpin(cx)).ok();
+            *self = Reservation::Held { _permit: permit };
+        }
+
+        Poll::Ready(())
+    }
+}
+
+fn to_permits(bytes: u64) -> u64 {
+    (bytes / PERMIT_SIZE) + (bytes % PERMIT_SIZE != 0) as u64
+}
+
+impl fmt::Debug for BodyBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BodyBudget")
+            .field("available", &self.permits.available_permits())
+            .field("total", &self.total)
+            .finish()
+    }
+}
@@ -244,7 +244,7 @@ macro_rules! impl_strict_from_form_field_from_capped {
 }
 
 macro_rules! impl_strict_from_data_from_capped {
-    ($T:ty) => (
+    ($T:ty, $limit:expr) => (
         #[crate::async_trait]
         impl<'r> $crate::data::FromData<'r> for $T {
             type Error = <$crate::data::Capped<Self> as $crate::data::FromData<'r>>::Error;
@@ -254,13 +254,19 @@ macro_rules! impl_strict_from_data_from_capped {
                 d: $crate::Data<'r>
             ) -> $crate::data::Outcome<'r, Self> {
                 use $crate::outcome::Outcome::*;
-                use std::io::{Error, ErrorKind::UnexpectedEof};
+                use std::io::{Error, ErrorKind::{InvalidInput, UnexpectedEof}};
+
+                // A capped value would be incomplete: don't read the body.
+                if let Err(status) = d.precheck_strict(($limit)(r, &d)) {
+                    let e = Error::new(InvalidInput, "data limit exceeded");
+                    return Failure((status, e.into()));
+                }
 
                 match <$crate::data::Capped<$T> as FromData>::from_data(r, d).await {
                     Success(p) if p.is_complete() => Success(p.into_inner()),
                     Success(_) => {
                         let e = Error::new(UnexpectedEof, "data limit exceeded");
-                        Failure((Status::BadRequest, e.into()))
+                        Failure((Status::PayloadTooLarge, e.into()))
                     },
                     Forward(d) => Forward(d),
                     Failure((s, e)) => Failure((s, e)),
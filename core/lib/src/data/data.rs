@@ -1,6 +1,9 @@
-use crate::tokio::io::AsyncReadExt;
-use crate::data::data_stream::DataStream;
-use crate::data::{ByteUnit, StreamReader};
+use std::io;
+
+use crate::tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+use crate::data::data_stream::{DataStream, Tee};
+use crate::data::{BodyBudget, ByteUnit, Capped, Limits, Peek, StreamReader};
+use crate::http::Status;
 
 /// The number of bytes to read into the "peek" buffer.
 pub const PEEK_BYTES: usize = 512;
@@ -41,18 +44,37 @@ pub struct Data<'r> {
     buffer: Vec<u8>,
     is_complete: bool,
     stream: StreamReader<'r>,
+    pub(crate) declared_len: Option<u64>,
+    pub(crate) expects_continue: bool,
+    pub(crate) limit: Option<ByteUnit>,
+    pub(crate) default_limit: ByteUnit,
+    pub(crate) budget: Option<BodyBudget>,
+    tee: Option<Tee<'r>>,
 }
 
 impl<'r> Data<'r> {
     /// Create a `Data` from a recognized `stream`.
     pub(crate) fn from<S: Into<StreamReader<'r>>>(stream: S) -> Data<'r> {
-        // TODO.async: This used to also set the read timeout to 5 seconds.
-        // Such a short read timeout is likely no longer necessary, but some
-        // kind of idle timeout should be implemented.
+        // NOTE: This used to also set the read timeout to 5 seconds. Stalled
+        // connections are instead closed by the configurable `idle_timeout`.
 
         let stream = stream.into();
         let buffer = Vec::with_capacity(PEEK_BYTES / 8);
-        Data { buffer, stream, is_complete: false }
+        Data {
+            buffer, stream,
+            is_complete: false,
+            declared_len: None,
+            expects_continue: false,
+            limit: None,
+            default_limit: Limits::BYTES,
+            budget: None,
+            tee: None,
+        }
+    }
+
+    /// Sets `flag` if reading the body fails because the connection was lost.
+    pub(crate) fn flag_aborted(&mut self, flag: std::sync::Arc<std::sync::atomic::AtomicBool>) {
+        self.stream.flag_aborted(flag);
     }
 
     /// This creates a `data` object from a local data source `data`.
@@ -62,9 +84,65 @@ impl<'r> Data<'r> {
             buffer: data,
             stream: StreamReader::empty(),
             is_complete: true,
+            declared_len: None,
+            expects_continue: false,
+            limit: None,
+            default_limit: Limits::BYTES,
+            budget: None,
+            tee: None,
         }
     }
 
+    /// Creates a `Data` whose body is `bytes`.
+    ///
+    /// This is intended for testing data guards directly, without a request.
+    /// All of `bytes` is immediately available: [`Data::peek()`] never reads
+    /// and [`Data::peek_complete()`] returns `true`. The data has no declared
+    /// length and route limit and doesn't expect a `100 Continue`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{Data, ToByteUnit};
+    ///
+    /// # rocket::async_test(async {
+    /// let mut data = Data::from_bytes(b"hello, world".to_vec());
+    /// assert_eq!(data.peek(5).await, b"hello");
+    ///
+    /// let string = data.open(1.kibibytes()).into_string().await.unwrap();
+    /// assert!(string.is_complete());
+    /// assert_eq!(string.into_inner(), "hello, world");
+    /// # });
+    /// ```
+    pub fn from_bytes(bytes: Vec<u8>) -> Data<'r> {
+        Data::local(bytes)
+    }
+
+    /// Creates a `Data` whose body is read from `reader`.
+    ///
+    /// This is intended for testing data guards directly, without a request,
+    /// or for bodies from custom transports. Unlike [`Data::from_bytes()`],
+    /// the body is streamed as it would be from a request: [`Data::peek()`]
+    /// reads from `reader` into the peek buffer. The data has no declared
+    /// length and route limit and doesn't expect a `100 Continue`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{Data, ToByteUnit};
+    ///
+    /// # rocket::async_test(async {
+    /// let reader: &[u8] = b"hello, world";
+    /// let data = Data::from_reader(reader);
+    /// let bytes = data.open(5.bytes()).into_bytes().await.unwrap();
+    /// assert!(!bytes.is_complete());
+    /// assert_eq!(bytes.into_inner(), b"hello");
+    /// # });
+    /// ```
+    pub fn from_reader<R: AsyncRead + Send + Sync + 'r>(reader: R) -> Data<'r> {
+        Data::from(StreamReader::reader(reader))
+    }
+
     /// Returns the raw data stream, limited to `limit` bytes.
     ///
     /// The stream contains all of the data in the body of the request,
@@ -72,6 +150,11 @@ impl<'r> Data<'r> {
     /// instance. This ensures that a `Data` type _always_ represents _all_ of
     /// the data in a request.
     ///
+    /// When [`Config::max_total_body_bytes`](crate::Config::max_total_body_bytes)
+    /// is set, the first read from the stream waits for the body to fit in the
+    /// global body budget. See [`DataStream`](DataStream#body-budget) for
+    /// details.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -83,7 +166,309 @@ impl<'r> Data<'r> {
     /// }
     /// ```
     pub fn open(self, limit: ByteUnit) -> DataStream<'r> {
-        DataStream::new(self.buffer, self.stream, limit.into())
+        let limit = limit.as_u64();
+        let reservation = self.budget.as_ref().map(|budget| {
+            let len = match self.is_complete {
+                true => Some(self.buffer.len() as u64),
+                false => self.declared_len,
+            };
+
+            budget.reserve(len.map_or(limit, |len| len.min(limit)))
+        });
+
+        let stream = DataStream::new(self.buffer, self.stream, limit).with_tee(self.tee);
+        match reservation {
+            Some(reservation) => stream.with_reservation(reservation),
+            None => stream,
+        }
+    }
+
+    /// Returns the raw data stream, limited to the route's limit, if it
+    /// declares one, or else to the default limit for the body's content type.
+    ///
+    /// The default limit is the configured limit named after the built-in data
+    /// guard for the request's `Content-Type`: `json` for JSON, `msgpack` for
+    /// MessagePack, `form` for URL-encoded forms, `data-form` for multipart
+    /// forms, and `string` for any `text/*` type. Bodies of any other type, or
+    /// without a `Content-Type`, are limited to `bytes`. See
+    /// [`Data::limit()`] for route limits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Data;
+    ///
+    /// async fn handler(data: Data<'_>) -> std::io::Result<()> {
+    ///     let bytes = data.open_default().into_bytes().await?;
+    ///     if !bytes.is_complete() {
+    ///         println!("the body was larger than the default limit");
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn open_default(self) -> DataStream<'r> {
+        let limit = self.limit.unwrap_or(self.default_limit);
+        self.open(limit)
+    }
+
+    /// Reads the body into a vector, limited to the lesser of `ceiling` and
+    /// the limit used by [`Data::open_default()`].
+    ///
+    /// The returned [`Capped`] indicates whether the entire body was read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{Data, ToByteUnit};
+    ///
+    /// async fn handler(data: Data<'_>) -> std::io::Result<Vec<u8>> {
+    ///     let bytes = data.open_capped(64.kibibytes()).await?;
+    ///     if !bytes.is_complete() {
+    ///         println!("the body was truncated");
+    ///     }
+    ///
+    ///     Ok(bytes.into_inner())
+    /// }
+    /// ```
+    pub async fn open_capped(self, ceiling: ByteUnit) -> io::Result<Capped<Vec<u8>>> {
+        let limit = std::cmp::min(self.limit.unwrap_or(self.default_limit), ceiling);
+        self.open(limit).into_bytes().await
+    }
+
+    /// Like [`Data::open()`] but invokes `callback` as the body is read to
+    /// report progress.
+    ///
+    /// After every read that yields data, `callback` is called with the total
+    /// number of bytes read so far and the declared length of the body, if
+    /// any, as reported by [`Data::declared_len()`]. The callback is called on
+    /// the read path and should thus return quickly; to report progress
+    /// elsewhere, send it over a channel.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{Data, ToByteUnit};
+    ///
+    /// async fn handler(data: Data<'_>) {
+    ///     let stream = data.open_with_progress(2.mebibytes(), |read, total| {
+    ///         match total {
+    ///             Some(total) => println!("read {} of {} bytes", read, total),
+    ///             None => println!("read {} bytes", read),
+    ///         }
+    ///     });
+    ///
+    ///     let bytes = stream.into_bytes().await;
+    /// }
+    /// ```
+    pub fn open_with_progress<F>(self, limit: ByteUnit, callback: F) -> DataStream<'r>
+        where F: FnMut(u64, Option<u64>) + Send + 'r
+    {
+        let total = self.declared_len;
+        self.open(limit).with_progress(total, callback)
+    }
+
+    /// Mirrors up to `cap` bytes of the body to `sink` as the body is read.
+    ///
+    /// Once the body is opened, every read from the returned [`DataStream`]
+    /// writes the bytes read, until `cap` bytes have been mirrored, to `sink`.
+    /// The body is thus recorded as it's read by whichever data guard reads
+    /// it, without being buffered. Bytes are mirrored in the order they're
+    /// read, including bytes read into the peek buffer. The sink is flushed
+    /// when the stream reaches its end.
+    ///
+    /// Reads wait for `sink` to accept the previously read bytes, so a slow
+    /// sink slows reading the body. If writing to `sink` fails, a warning is
+    /// logged and mirroring stops; reading the body is unaffected. Bytes read
+    /// but not yet written when the stream is dropped are discarded. Calling
+    /// this method again replaces the previous sink.
+    ///
+    /// # Example
+    ///
+    /// Record the first 1KiB of every request body in a fairing:
+    ///
+    /// ```rust
+    /// use rocket::{Request, Data};
+    /// use rocket::data::ToByteUnit;
+    /// use rocket::fairing::{Fairing, Info, Kind};
+    /// # use rocket::tokio::io::sink as audit_log;
+    ///
+    /// struct Audit;
+    ///
+    /// #[rocket::async_trait]
+    /// impl Fairing for Audit {
+    ///     fn info(&self) -> Info {
+    ///         Info { name: "Body Audit", kind: Kind::Request }
+    ///     }
+    ///
+    ///     async fn on_request(&self, req: &mut Request<'_>, data: &mut Data<'_>) {
+    ///         data.tee(audit_log(), 1.kibibytes());
+    ///     }
+    /// }
+    /// ```
+    pub fn tee<W: AsyncWrite + Send + Sync + 'r>(&mut self, sink: W, cap: ByteUnit) {
+        self.tee = Some(Tee::new(sink, cap.as_u64()));
+    }
+
+    /// Returns the length of the body as declared by the client in the
+    /// `Content-Length` header, if any.
+    ///
+    /// The declared length is advisory: no data is read to verify it. Use
+    /// [`Data::open()`] with a limit to read the body safely.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Data;
+    ///
+    /// fn handler(data: Data<'_>) {
+    ///     if let Some(len) = data.declared_len() {
+    ///         println!("the client says the body is {} bytes", len);
+    ///     }
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn declared_len(&self) -> Option<u64> {
+        self.declared_len
+    }
+
+    /// Returns the data limit of the route handling the request, if it
+    /// declares one.
+    ///
+    /// A route declares a limit by name with the `limit` route attribute
+    /// parameter, or by setting [`Route::limit`](crate::Route::limit). The name
+    /// is resolved against the configured [`Limits`](crate::data::Limits)
+    /// before the data guard runs. Built-in data guards, such as `String`,
+    /// `Vec<u8>`, and `Json`, prefer this limit over their own default. Custom
+    /// data guards should do the same.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::data::{Data, ToByteUnit};
+    ///
+    /// #[post("/upload", data = "<data>", limit = "upload")]
+    /// async fn upload(data: Data<'_>) -> std::io::Result<String> {
+    ///     let limit = data.limit().unwrap_or(1.mebibytes());
+    ///     let bytes = data.open(limit).into_bytes().await?;
+    ///     Ok(format!("read {} bytes", bytes.len()))
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn limit(&self) -> Option<ByteUnit> {
+        self.limit
+    }
+
+    /// Returns `true` if the client sent an `Expect: 100-continue` header and
+    /// is thus waiting on an interim `100 Continue` response before sending
+    /// the body.
+    ///
+    /// The interim response is sent automatically the first time body data is
+    /// read, by [`Data::peek()`] or through [`Data::open()`]. A request can be
+    /// rejected _without_ the body being transferred by failing before then.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Data;
+    ///
+    /// fn handler(data: Data<'_>) {
+    ///     if data.expects_continue() {
+    ///         println!("the client is waiting before sending the body");
+    ///     }
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn expects_continue(&self) -> bool {
+        self.expects_continue
+    }
+
+    /// Checks, without reading any data, whether the body should be rejected
+    /// outright given the `limit` a guard intends to read with.
+    ///
+    /// Returns `Err(Status::ExpectationFailed)` if the client is waiting on a
+    /// `100 Continue` ([`Data::expects_continue()`]) for a body whose declared
+    /// length ([`Data::declared_len()`]) exceeds `limit`. Failing with the
+    /// returned status before reading the body means that the body is never
+    /// transferred. Returns `Ok(())` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Request;
+    /// use rocket::data::{self, Data, FromData, ToByteUnit};
+    /// # struct MyType;
+    /// # type MyError = ();
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromData<'r> for MyType {
+    ///     type Error = MyError;
+    ///
+    ///     async fn from_data(r: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+    ///         let limit = 1.mebibytes();
+    ///         if let Err(status) = data.precheck(limit) {
+    ///             return data::Outcome::Failure((status, ()));
+    ///         }
+    ///
+    ///         /* .. */
+    ///         # unimplemented!()
+    ///     }
+    /// }
+    /// ```
+    pub fn precheck(&self, limit: ByteUnit) -> Result<(), Status> {
+        match self.declared_len {
+            Some(len) if self.expects_continue && len > limit.as_u64() => {
+                Err(Status::ExpectationFailed)
+            }
+            _ => Ok(())
+        }
+    }
+
+    /// Like [`Data::precheck()`], but for guards that reject a body exceeding
+    /// `limit` rather than reading a prefix of it.
+    ///
+    /// In addition to the checks performed by `precheck()`, returns
+    /// `Err(Status::PayloadTooLarge)` if the client declared a length
+    /// ([`Data::declared_len()`]) that exceeds `limit`. Such a body would be
+    /// rejected once `limit` bytes had been read; failing with the returned
+    /// status before reading any data means that no bandwidth is wasted on it.
+    /// A body without a declared length, such as a chunked body, is still only
+    /// limited as it is read.
+    ///
+    /// Built-in guards that require a complete body, such as `String`,
+    /// `Json`, and `Form`, check their limit with this method. Their
+    /// [`Capped`](crate::data::Capped) counterparts use `precheck()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::Request;
+    /// use rocket::data::{self, Data, FromData, ToByteUnit};
+    /// # struct MyType;
+    /// # type MyError = ();
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromData<'r> for MyType {
+    ///     type Error = MyError;
+    ///
+    ///     async fn from_data(r: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+    ///         let limit = data.limit().unwrap_or(1.mebibytes());
+    ///         if let Err(status) = data.precheck_strict(limit) {
+    ///             return data::Outcome::Failure((status, ()));
+    ///         }
+    ///
+    ///         /* .. */
+    ///         # unimplemented!()
+    ///     }
+    /// }
+    /// ```
+    pub fn precheck_strict(&self, limit: ByteUnit) -> Result<(), Status> {
+        self.precheck(limit)?;
+        match self.declared_len {
+            Some(len) if len > limit.as_u64() => Err(Status::PayloadTooLarge),
+            _ => Ok(())
+        }
     }
 
     /// Retrieve at most `num` bytes from the `peek` buffer without consuming
@@ -167,6 +552,86 @@ impl<'r> Data<'r> {
         &self.buffer[..std::cmp::min(len, num)]
     }
 
+    /// Like [`Data::peek()`], but returns a [`Peek`] that also reports
+    /// whether the end of the body was reached and whether the number of
+    /// bytes returned was capped at [`PEEK_BYTES`].
+    ///
+    /// A slice returned by `peek()` that is shorter than requested may be
+    /// short because the body is, because more than `PEEK_BYTES` bytes were
+    /// requested, or because reading failed. This method tells these apart,
+    /// which matters to guards that sniff a protocol from the body's prefix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Data;
+    ///
+    /// # rocket::async_test(async {
+    /// let reader: &[u8] = b"hello";
+    /// let mut data = Data::from_reader(reader);
+    ///
+    /// let peek = data.peek_detailed(3).await;
+    /// assert_eq!(peek.bytes(), b"hel");
+    /// assert!(!peek.is_capped());
+    ///
+    /// let peek = data.peek_detailed(10).await;
+    /// assert_eq!(peek.bytes(), b"hello");
+    /// assert!(peek.is_eof());
+    /// # });
+    /// ```
+    pub async fn peek_detailed(&mut self, num: usize) -> Peek<'_> {
+        self.peek(num).await;
+
+        let len = std::cmp::min(self.buffer.len(), std::cmp::min(num, PEEK_BYTES));
+        let capped = num > PEEK_BYTES
+            && len == PEEK_BYTES
+            && !(self.is_complete && self.buffer.len() == PEEK_BYTES);
+
+        Peek { bytes: &self.buffer[..len], eof: self.is_complete, capped }
+    }
+
+    /// Returns the body data already buffered by previous calls to
+    /// [`Data::peek()`] without reading any more data.
+    ///
+    /// Unlike `peek()`, this method never performs I/O, and so never waits.
+    /// The returned slice is typically empty before the body is first peeked,
+    /// though the body of a local request is buffered in its entirety, and it
+    /// may be longer than the `num` bytes previously requested via `peek()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Data;
+    ///
+    /// async fn handler(mut data: Data<'_>) {
+    ///     let peeked = data.peek(10).await.len();
+    ///     assert!(data.buffered().len() >= peeked);
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns the number of bytes of body data already buffered by previous
+    /// calls to [`Data::peek()`]. Equivalent to `data.buffered().len()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Data;
+    ///
+    /// fn handler(data: Data<'_>) {
+    ///     if data.buffered_len() > 0 {
+    ///         println!("buffered: {:?}", data.buffered());
+    ///     }
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
     /// Returns true if the `peek` buffer contains all of the data in the body
     /// of the request. Returns `false` if it does not or if it is not known if
     /// it does.
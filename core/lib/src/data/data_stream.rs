@@ -2,15 +2,19 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::path::Path;
 use std::io::{self, Cursor};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use tokio::fs::File;
 use tokio::io::{AsyncRead, AsyncWrite, AsyncReadExt, ReadBuf, Take};
+use tokio_util::io::ReaderStream;
 use futures::stream::Stream;
 use futures::ready;
 
 use crate::http::hyper;
 use crate::ext::{PollExt, Chain};
 use crate::data::{Capped, N};
+use crate::data::budget::Reservation;
 
 /// Raw data stream of a request body.
 ///
@@ -39,14 +43,52 @@ use crate::data::{Capped, N};
 ///
 /// [`DataStream::stream_to(&mut vec)`]: DataStream::stream_to()
 /// [`DataStream::stream_to(&mut file)`]: DataStream::stream_to()
+///
+/// # Errors
+///
+/// If the connection to the client is lost while the body is being read, as
+/// when the client disconnects mid-body, reads fail with an error of kind
+/// [`ConnectionAborted`](io::ErrorKind::ConnectionAborted). Data guards
+/// should propagate such errors as a failure rather than, say, treating the
+/// body as invalid. No response is sent for such a request, and no error
+/// catcher is invoked if routing subsequently fails or forwards.
+///
+/// # Body Budget
+///
+/// When [`Config::max_total_body_bytes`](crate::Config::max_total_body_bytes)
+/// is nonzero, the first read from a request's `DataStream` waits until the
+/// stream can reserve as many bytes of the global body budget as it may read:
+/// the lesser of its limit and the body's declared length. The reservation is
+/// released when the stream is dropped.
 pub struct DataStream<'r> {
     pub(crate) chain: Take<Chain<Cursor<Vec<u8>>, StreamReader<'r>>>,
+    progress: Option<Progress<'r>>,
+    reservation: Option<Reservation>,
+    tee: Option<Tee<'r>>,
+}
+
+/// A sink to which bytes read from a stream are mirrored, up to a cap.
+pub(crate) struct Tee<'r> {
+    sink: Pin<Box<dyn AsyncWrite + Send + Sync + 'r>>,
+    /// The number of bytes that may still be mirrored.
+    remaining: u64,
+    /// Bytes read from the stream but not yet written to `sink`.
+    pending: Vec<u8>,
+}
+
+/// A progress callback and the state it reports.
+struct Progress<'r> {
+    read: u64,
+    total: Option<u64>,
+    callback: Box<dyn FnMut(u64, Option<u64>) + Send + 'r>,
 }
 
 /// An adapter: turns a `T: Stream` (in `StreamKind`) into a `tokio::AsyncRead`.
 pub struct StreamReader<'r> {
     state: State,
     inner: StreamKind<'r>,
+    /// Set when a read fails because the connection was lost mid-body.
+    aborted: Option<Arc<AtomicBool>>,
 }
 
 /// The current state of `StreamReader` `AsyncRead` adapter.
@@ -60,22 +102,43 @@ enum State {
 enum StreamKind<'r> {
     Empty,
     Body(&'r mut hyper::Body),
-    Multipart(multer::Field<'r>)
+    Multipart(multer::Field<'r>),
+    Reader(ReaderStream<Pin<Box<dyn AsyncRead + Send + Sync + 'r>>>),
 }
 
 impl<'r> DataStream<'r> {
     pub(crate) fn new(buf: Vec<u8>, stream: StreamReader<'r>, limit: u64) -> Self {
         let chain = Chain::new(Cursor::new(buf), stream).take(limit);
-        Self { chain }
+        Self { chain, progress: None, reservation: None, tee: None }
+    }
+
+    pub(crate) fn with_tee(mut self, tee: Option<Tee<'r>>) -> Self {
+        self.tee = tee;
+        self
+    }
+
+    pub(crate) fn with_reservation(mut self, reservation: Reservation) -> Self {
+        self.reservation = Some(reservation);
+        self
+    }
+
+    pub(crate) fn with_progress<F>(mut self, total: Option<u64>, callback: F) -> Self
+        where F: FnMut(u64, Option<u64>) + Send + 'r
+    {
+        self.progress = Some(Progress { read: 0, total, callback: Box::new(callback) });
+        self
     }
 
     /// Whether a previous read exhausted the set limit _and then some_.
+    ///
+    /// The probe reads from the underlying chain directly: the probed byte is
+    /// never handed to the reader, so it isn't reported or mirrored.
     async fn limit_exceeded(&mut self) -> io::Result<bool> {
         #[cold]
         async fn _limit_exceeded(stream: &mut DataStream<'_>) -> io::Result<bool> {
             stream.chain.set_limit(1);
             let mut buf = [0u8; 1];
-            Ok(stream.read(&mut buf).await? != 0)
+            Ok(stream.chain.read(&mut buf).await? != 0)
         }
 
         Ok(self.chain.limit() == 0 && _limit_exceeded(self).await?)
@@ -227,21 +290,31 @@ impl<'r> DataStream<'r> {
 
 // TODO.async: Consider implementing `AsyncBufRead`.
 
-impl StreamReader<'_> {
+impl<'r> StreamReader<'r> {
     pub fn empty() -> Self {
-        Self { inner: StreamKind::Empty, state: State::Done }
+        Self { inner: StreamKind::Empty, state: State::Done, aborted: None }
+    }
+
+    pub fn reader<R: AsyncRead + Send + Sync + 'r>(reader: R) -> Self {
+        let inner = StreamKind::Reader(ReaderStream::new(Box::pin(reader)));
+        Self { inner, state: State::Pending, aborted: None }
+    }
+
+    /// Sets `flag` if a read fails because the connection was lost.
+    pub(crate) fn flag_aborted(&mut self, flag: Arc<AtomicBool>) {
+        self.aborted = Some(flag);
     }
 }
 
 impl<'r> From<&'r mut hyper::Body> for StreamReader<'r> {
     fn from(body: &'r mut hyper::Body) -> Self {
-        Self { inner: StreamKind::Body(body), state: State::Pending }
+        Self { inner: StreamKind::Body(body), state: State::Pending, aborted: None }
     }
 }
 
 impl<'r> From<multer::Field<'r>> for StreamReader<'r> {
     fn from(field: multer::Field<'r>) -> Self {
-        Self { inner: StreamKind::Multipart(field), state: State::Pending }
+        Self { inner: StreamKind::Multipart(field), state: State::Pending, aborted: None }
     }
 }
 
@@ -252,7 +325,86 @@ impl AsyncRead for DataStream<'_> {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
-        Pin::new(&mut self.chain).poll_read(cx, buf)
+        if let Some(ref mut reservation) = self.reservation {
+            ready!(reservation.poll_acquire(cx));
+        }
+
+        // Mirror previously read bytes before reading more: the tee buffers
+        // at most one read's worth of data.
+        if let Some(ref mut tee) = self.tee {
+            if ready!(tee.poll_drain(cx)).is_err() {
+                self.tee = None;
+            }
+        }
+
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut self.chain).poll_read(cx, buf))?;
+        let new = &buf.filled()[filled..];
+        if let Some(ref mut progress) = self.progress {
+            if !new.is_empty() {
+                progress.read += new.len() as u64;
+                (progress.callback)(progress.read, progress.total);
+            }
+        }
+
+        if let Some(ref mut tee) = self.tee {
+            let result = match new.is_empty() {
+                true => ready!(tee.poll_finish(cx)),
+                false => {
+                    tee.push(new);
+                    match tee.poll_drain(cx) {
+                        Poll::Ready(result) => result,
+                        Poll::Pending => Ok(()),
+                    }
+                }
+            };
+
+            if result.is_err() {
+                self.tee = None;
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'r> Tee<'r> {
+    pub(crate) fn new<W: AsyncWrite + Send + Sync + 'r>(sink: W, cap: u64) -> Self {
+        Tee { sink: Box::pin(sink), remaining: cap, pending: vec![] }
+    }
+
+    /// Queues as many of `bytes` as the cap allows to be mirrored.
+    fn push(&mut self, bytes: &[u8]) {
+        let n = std::cmp::min(bytes.len() as u64, self.remaining) as usize;
+        self.pending.extend_from_slice(&bytes[..n]);
+        self.remaining -= n as u64;
+    }
+
+    /// Writes all pending bytes to the sink.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.pending.is_empty() {
+            let result = ready!(self.sink.as_mut().poll_write(cx, &self.pending));
+            match result {
+                Ok(0) => return Poll::Ready(Err(self.failed(io::ErrorKind::WriteZero.into()))),
+                Ok(n) => drop(self.pending.drain(..n)),
+                Err(e) => return Poll::Ready(Err(self.failed(e))),
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Writes all pending bytes to the sink and flushes it.
+    fn poll_finish(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_drain(cx))?;
+        ready!(self.sink.as_mut().poll_flush(cx)).map_err(|e| self.failed(e))?;
+        Poll::Ready(Ok(()))
+    }
+
+    #[cold]
+    fn failed(&self, e: io::Error) -> io::Error {
+        warn_!("Failed to mirror request body: {}. Stopping tee.", e);
+        e
     }
 }
 
@@ -265,9 +417,10 @@ impl Stream for StreamKind<'_> {
     ) -> Poll<Option<Self::Item>> {
         match self.get_mut() {
             StreamKind::Body(body) => Pin::new(body).poll_next(cx)
-                .map_err_ext(|e| io::Error::new(io::ErrorKind::Other, e)),
+                .map_err_ext(body_error),
             StreamKind::Multipart(mp) => Pin::new(mp).poll_next(cx)
                 .map_err_ext(|e| io::Error::new(io::ErrorKind::Other, e)),
+            StreamKind::Reader(reader) => Pin::new(reader).poll_next(cx),
             StreamKind::Empty => Poll::Ready(None),
         }
     }
@@ -276,11 +429,28 @@ impl Stream for StreamKind<'_> {
         match self {
             StreamKind::Body(body) => body.size_hint(),
             StreamKind::Multipart(mp) => mp.size_hint(),
+            StreamKind::Reader(reader) => reader.size_hint(),
             StreamKind::Empty => (0, Some(0)),
         }
     }
 }
 
+/// Converts a Hyper body error into an `io::Error`. Unless the body itself is
+/// malformed, the connection was lost, and the error is `ConnectionAborted`.
+fn body_error(e: hyper::Error) -> io::Error {
+    use std::error::Error;
+    use io::ErrorKind::*;
+
+    let io_kind = e.source()
+        .and_then(|source| source.downcast_ref::<io::Error>())
+        .map(|e| e.kind());
+
+    match e.is_parse() || matches!(io_kind, Some(InvalidInput | InvalidData)) {
+        true => io::Error::new(Other, e),
+        false => io::Error::new(ConnectionAborted, e),
+    }
+}
+
 impl AsyncRead for StreamReader<'_> {
     fn poll_read(
         mut self: Pin<&mut Self>,
@@ -291,7 +461,15 @@ impl AsyncRead for StreamReader<'_> {
             self.state = match self.state {
                 State::Pending => {
                     match ready!(Pin::new(&mut self.inner).poll_next(cx)) {
-                        Some(Err(e)) => return Poll::Ready(Err(e)),
+                        Some(Err(e)) => {
+                            if e.kind() == io::ErrorKind::ConnectionAborted {
+                                if let Some(ref flag) = self.aborted {
+                                    flag.store(true, Ordering::Release);
+                                }
+                            }
+
+                            return Poll::Ready(Err(e));
+                        }
                         Some(Ok(bytes)) => State::Partial(Cursor::new(bytes)),
                         None => State::Done,
                     }
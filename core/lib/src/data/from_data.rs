@@ -1,6 +1,6 @@
 use crate::http::{RawStr, Status};
 use crate::request::{Request, local_cache};
-use crate::data::{Data, Limits};
+use crate::data::{Data, Limits, ByteUnit};
 use crate::outcome::{self, IntoOutcome, try_outcome, Outcome::*};
 
 /// Type alias for the `Outcome` of [`FromData`].
@@ -196,17 +196,36 @@ pub trait FromData<'r>: Sized {
 
 use crate::data::Capped;
 
+/// The limit for string data guards: the route's limit or the `string` limit.
+fn string_limit(req: &Request<'_>, data: &Data<'_>) -> ByteUnit {
+    data.limit()
+        .or_else(|| req.limits().get("string"))
+        .unwrap_or(Limits::STRING)
+}
+
+/// The limit for byte data guards: the route's limit or the `bytes` limit.
+fn bytes_limit(req: &Request<'_>, data: &Data<'_>) -> ByteUnit {
+    data.limit()
+        .or_else(|| req.limits().get("bytes"))
+        .unwrap_or(Limits::BYTES)
+}
+
 #[crate::async_trait]
 impl<'r> FromData<'r> for Capped<String> {
     type Error = std::io::Error;
 
     async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
-        let limit = req.limits().get("string").unwrap_or(Limits::STRING);
+        let limit = string_limit(req, &data);
+        if let Err(status) = data.precheck(limit) {
+            let e = std::io::Error::new(std::io::ErrorKind::InvalidInput, "data limit exceeded");
+            return Failure((status, e));
+        }
+
         data.open(limit).into_string().await.into_outcome(Status::BadRequest)
     }
 }
 
-impl_strict_from_data_from_capped!(String);
+impl_strict_from_data_from_capped!(String, string_limit);
 
 #[crate::async_trait]
 impl<'r> FromData<'r> for Capped<&'r str> {
@@ -219,7 +238,7 @@ impl<'r> FromData<'r> for Capped<&'r str> {
     }
 }
 
-impl_strict_from_data_from_capped!(&'r str);
+impl_strict_from_data_from_capped!(&'r str, string_limit);
 
 #[crate::async_trait]
 impl<'r> FromData<'r> for Capped<&'r RawStr> {
@@ -232,7 +251,7 @@ impl<'r> FromData<'r> for Capped<&'r RawStr> {
     }
 }
 
-impl_strict_from_data_from_capped!(&'r RawStr);
+impl_strict_from_data_from_capped!(&'r RawStr, string_limit);
 
 #[crate::async_trait]
 impl<'r> FromData<'r> for Capped<std::borrow::Cow<'_, str>> {
@@ -244,7 +263,7 @@ impl<'r> FromData<'r> for Capped<std::borrow::Cow<'_, str>> {
     }
 }
 
-impl_strict_from_data_from_capped!(std::borrow::Cow<'_, str>);
+impl_strict_from_data_from_capped!(std::borrow::Cow<'_, str>, string_limit);
 
 #[crate::async_trait]
 impl<'r> FromData<'r> for Capped<&'r [u8]> {
@@ -257,19 +276,24 @@ impl<'r> FromData<'r> for Capped<&'r [u8]> {
     }
 }
 
-impl_strict_from_data_from_capped!(&'r [u8]);
+impl_strict_from_data_from_capped!(&'r [u8], bytes_limit);
 
 #[crate::async_trait]
 impl<'r> FromData<'r> for Capped<Vec<u8>> {
     type Error = std::io::Error;
 
     async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
-        let limit = req.limits().get("bytes").unwrap_or(Limits::BYTES);
+        let limit = bytes_limit(req, &data);
+        if let Err(status) = data.precheck(limit) {
+            let e = std::io::Error::new(std::io::ErrorKind::InvalidInput, "data limit exceeded");
+            return Failure((status, e));
+        }
+
         data.open(limit).into_bytes().await.into_outcome(Status::BadRequest)
     }
 }
 
-impl_strict_from_data_from_capped!(Vec<u8>);
+impl_strict_from_data_from_capped!(Vec<u8>, bytes_limit);
 
 #[crate::async_trait]
 impl<'r> FromData<'r> for Data<'r> {
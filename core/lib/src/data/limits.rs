@@ -4,6 +4,7 @@ use serde::{Serialize, Deserialize};
 use crate::request::{Request, FromRequest, Outcome};
 
 use crate::data::ByteUnit;
+use crate::http::ContentType;
 use crate::http::uncased::Uncased;
 
 /// Mapping from (hierarchical) data types to size limits.
@@ -299,6 +300,23 @@ impl Limits {
 
         None
     }
+
+    /// Returns the limit conventionally applied to a body of type
+    /// `content_type`: the limit named after the built-in data guard for that
+    /// type, falling back to the guard's default if the name isn't configured.
+    /// Bodies of other or unknown types use the `bytes` limit.
+    pub(crate) fn for_content_type(&self, content_type: Option<&ContentType>) -> ByteUnit {
+        let (name, default) = match content_type {
+            Some(ct) if ct.is_json() => ("json", Limits::JSON),
+            Some(ct) if ct.is_msgpack() => ("msgpack", Limits::MESSAGE_PACK),
+            Some(ct) if ct.is_form() => ("form", Limits::FORM),
+            Some(ct) if ct.is_form_data() => ("data-form", Limits::DATA_FORM),
+            Some(ct) if ct.top() == "text" => ("string", Limits::STRING),
+            _ => ("bytes", Limits::BYTES),
+        };
+
+        self.get(name).unwrap_or(default)
+    }
 }
 
 impl fmt::Display for Limits {
@@ -2,16 +2,22 @@
 
 #[macro_use]
 mod capped;
+mod budget;
 mod data;
 mod data_stream;
 mod from_data;
 mod limits;
+mod peek;
+mod with_raw;
 
-pub use self::data::Data;
+pub use self::data::{Data, PEEK_BYTES};
 pub use self::data_stream::DataStream;
 pub use self::from_data::{FromData, Outcome};
 pub use self::limits::Limits;
+pub use self::peek::Peek;
+pub use self::with_raw::{WithRaw, WithRawError};
 pub use self::capped::{N, Capped};
 pub use ubyte::{ByteUnit, ToByteUnit};
 
 pub(crate) use self::data_stream::StreamReader;
+pub(crate) use self::budget::BodyBudget;
@@ -0,0 +1,117 @@
+/// The result of peeking into a body via [`Data::peek_detailed()`].
+///
+/// A peek can return fewer bytes than requested for one of three reasons,
+/// which `Peek` tells apart:
+///
+///   * The body is shorter than requested: [`Peek::is_eof()`] is `true`.
+///   * More than [`PEEK_BYTES`] were requested: [`Peek::is_capped()`] is
+///     `true`, and exactly `PEEK_BYTES` bytes were returned.
+///   * Reading the body failed: neither is `true`.
+///
+/// [`Data::peek_detailed()`]: crate::Data::peek_detailed()
+/// [`PEEK_BYTES`]: crate::data::PEEK_BYTES
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::data::Data;
+///
+/// async fn sniff(mut data: Data<'_>) {
+///     let peek = data.peek_detailed(4).await;
+///     if peek.bytes() == b"\x89PNG" {
+///         /* the body is likely a PNG */
+///     } else if peek.is_eof() {
+///         /* the body is shorter than 4 bytes */
+///     }
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Peek<'a> {
+    pub(crate) bytes: &'a [u8],
+    pub(crate) eof: bool,
+    pub(crate) capped: bool,
+}
+
+impl<'a> Peek<'a> {
+    /// The peeked bytes: at most the number requested and at most
+    /// [`PEEK_BYTES`].
+    ///
+    /// [`PEEK_BYTES`]: crate::data::PEEK_BYTES
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Data;
+    ///
+    /// # rocket::async_test(async {
+    /// let mut data = Data::from_bytes(b"hello".to_vec());
+    /// assert_eq!(data.peek_detailed(2).await.bytes(), b"he");
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns `true` if the entire body is known to have been read into the
+    /// peek buffer. Thus, if fewer bytes were returned than requested and
+    /// the request was not capped, the body is shorter than requested.
+    ///
+    /// When exactly the number of bytes requested is returned, the end of
+    /// the body may not have been reached yet, and this method may return
+    /// `false` even if the body is no longer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::Data;
+    ///
+    /// # rocket::async_test(async {
+    /// let reader: &[u8] = b"hi";
+    /// let mut data = Data::from_reader(reader);
+    /// let peek = data.peek_detailed(5).await;
+    /// assert_eq!(peek.bytes(), b"hi");
+    /// assert!(peek.is_eof());
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn is_eof(&self) -> bool {
+        self.eof
+    }
+
+    /// Returns `true` if more than [`PEEK_BYTES`] were requested and the body
+    /// may be longer than `PEEK_BYTES`, so that only `PEEK_BYTES` bytes were
+    /// returned.
+    ///
+    /// [`PEEK_BYTES`]: crate::data::PEEK_BYTES
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::{Data, PEEK_BYTES};
+    ///
+    /// # rocket::async_test(async {
+    /// let reader: &[u8] = &[0; 1024];
+    /// let mut data = Data::from_reader(reader);
+    /// let peek = data.peek_detailed(1024).await;
+    /// assert_eq!(peek.bytes().len(), PEEK_BYTES);
+    /// assert!(peek.is_capped());
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn is_capped(&self) -> bool {
+        self.capped
+    }
+}
+
+impl AsRef<[u8]> for Peek<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl PartialEq<[u8]> for Peek<'_> {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.bytes == other
+    }
+}
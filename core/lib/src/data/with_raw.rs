@@ -0,0 +1,148 @@
+use std::io::{self, Cursor};
+use std::ops::Deref;
+
+use crate::request::Request;
+use crate::outcome::Outcome::*;
+use crate::data::{Data, FromData, Outcome};
+use crate::http::Status;
+use crate::http::hyper::body::Bytes;
+
+/// A data guard that parses a `T` from the body while retaining the raw body.
+///
+/// Data guards consume the body as they parse it, so the raw bytes a value
+/// was parsed from are usually lost. Some applications need both, for
+/// instance to verify a webhook's signature, which is computed over the exact
+/// bytes sent, before trusting the parsed payload. `WithRaw<T>` reads the
+/// body into memory once, then runs `T`'s [`FromData`] implementation over the
+/// buffered bytes. Both the raw bytes, via [`WithRaw::raw_bytes()`], and the
+/// parsed value, via `Deref` or [`WithRaw::into_inner()`], are available.
+///
+/// # Limits
+///
+/// The body is buffered up to the limit [`Data::open_default()`] applies:
+/// the route's limit, if any, and otherwise the limit conventionally applied
+/// to the request's `Content-Type`, such as the `json` limit for JSON bodies.
+/// A body exceeding the limit fails with `413 Payload Too Large`. `T` parses
+/// the buffered body with its own limit, as it would the request's body.
+///
+/// # Outcome
+///
+/// If `T` succeeds, so does `WithRaw<T>`. If `T` fails, `WithRaw<T>` fails
+/// with the same status and [`WithRawError::Inner`]. If `T` forwards, so does
+/// `WithRaw<T>`, forwarding the buffered body.
+///
+/// # Example
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::data::WithRaw;
+/// use rocket::form::Form;
+///
+/// # fn verify(_: &[u8]) -> bool { true }
+/// #[derive(FromForm)]
+/// struct Event<'r> {
+///     kind: &'r str,
+/// }
+///
+/// #[post("/hook", data = "<event>")]
+/// fn hook(event: WithRaw<Form<Event<'_>>>) -> &'static str {
+///     if !verify(event.raw_bytes()) {
+///         return "bad signature";
+///     }
+///
+///     match event.kind {
+///         "ping" => "pong",
+///         _ => "ok",
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WithRaw<T> {
+    raw: Bytes,
+    value: T,
+}
+
+/// Error returned by the [`WithRaw`] data guard.
+#[derive(Debug)]
+pub enum WithRawError<E> {
+    /// Reading the body failed or the body exceeded the limit.
+    Io(io::Error),
+    /// The inner data guard failed.
+    Inner(E),
+}
+
+impl<T> WithRaw<T> {
+    /// Returns the raw body the value was parsed from.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::WithRaw;
+    ///
+    /// fn signature_input(body: &WithRaw<String>) -> &[u8] {
+    ///     body.raw_bytes()
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn raw_bytes(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Consumes `self` and returns the parsed value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::data::WithRaw;
+    ///
+    /// fn value(body: WithRaw<String>) -> String {
+    ///     body.into_inner()
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for WithRaw<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+#[crate::async_trait]
+impl<'r, T: FromData<'r>> FromData<'r> for WithRaw<T> {
+    type Error = WithRawError<T::Error>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        let (limit, default_limit) = (data.limit, data.default_limit);
+        let raw_limit = limit.unwrap_or(default_limit);
+        if let Err(status) = data.precheck_strict(raw_limit) {
+            let e = io::Error::new(io::ErrorKind::InvalidInput, "data limit exceeded");
+            return Failure((status, WithRawError::Io(e)));
+        }
+
+        let raw = match data.open(raw_limit).into_bytes().await {
+            Ok(bytes) if bytes.is_complete() => Bytes::from(bytes.into_inner()),
+            Ok(_) => {
+                let e = io::Error::new(io::ErrorKind::InvalidData, "data limit exceeded");
+                return Failure((Status::PayloadTooLarge, WithRawError::Io(e)));
+            }
+            Err(e) => return Failure((Status::BadRequest, WithRawError::Io(e))),
+        };
+
+        let mut buffered = Data::from_reader(Cursor::new(raw.clone()));
+        buffered.declared_len = Some(raw.len() as u64);
+        buffered.limit = limit;
+        buffered.default_limit = default_limit;
+        match T::from_data(req, buffered).await {
+            Success(value) => Success(WithRaw { raw, value }),
+            Failure((status, e)) => Failure((status, WithRawError::Inner(e))),
+            Forward(data) => Forward(data),
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use std::{io, time::Duration};
 use std::task::{Poll, Context};
 use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use bytes::{Bytes, BytesMut};
 use pin_project_lite::pin_project;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
-use tokio::time::{sleep, Sleep};
+use tokio::time::{sleep, Instant, Sleep};
 
 use futures::stream::Stream;
 use futures::future::{self, Future, FutureExt};
@@ -137,6 +139,41 @@ enum State {
     Mercy(Pin<Box<Sleep>>),
 }
 
+/// Detects a connection that has been idle, without I/O activity or in-flight
+/// requests, for `timeout`.
+struct Idle {
+    timeout: Duration,
+    timer: Pin<Box<Sleep>>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Idle {
+    fn new(timeout: Duration) -> Self {
+        Idle { timeout, timer: Box::pin(sleep(timeout)), in_flight: Arc::default() }
+    }
+
+    fn reset(&mut self) {
+        let deadline = Instant::now() + self.timeout;
+        self.timer.as_mut().reset(deadline);
+    }
+
+    /// Returns `true` if the timer has expired with no requests in-flight.
+    /// Otherwise, ensures the timer is registered to wake the task.
+    fn poll_expired(&mut self, cx: &mut Context<'_>) -> bool {
+        if self.timer.as_mut().poll(cx).is_pending() {
+            return false;
+        }
+
+        if self.in_flight.load(Ordering::Acquire) == 0 {
+            return true;
+        }
+
+        self.reset();
+        let _ = self.timer.as_mut().poll(cx);
+        false
+    }
+}
+
 pin_project! {
     /// I/O that can be cancelled when a future `F` resolves.
     #[must_use = "futures do nothing unless polled"]
@@ -148,16 +185,24 @@ pin_project! {
         state: State,
         grace: Duration,
         mercy: Duration,
+        idle: Option<Idle>,
     }
 }
 
 impl<F: Future, I: AsyncWrite> CancellableIo<F, I> {
-    pub fn new(trigger: F, io: I, grace: Duration, mercy: Duration) -> Self {
+    pub fn new(
+        trigger: F,
+        io: I,
+        grace: Duration,
+        mercy: Duration,
+        idle_timeout: Option<Duration>,
+    ) -> Self {
         CancellableIo {
             grace, mercy,
             io: Some(io),
             trigger: trigger.fuse(),
             state: State::Active,
+            idle: idle_timeout.map(Idle::new),
         }
     }
 
@@ -165,6 +210,13 @@ impl<F: Future, I: AsyncWrite> CancellableIo<F, I> {
         self.io.as_ref()
     }
 
+    /// The counter of requests in-flight on this connection, if the connection
+    /// closes when idle. The connection is never considered idle while the
+    /// counter is non-zero.
+    pub fn in_flight(&self) -> Option<Arc<AtomicUsize>> {
+        self.idle.as_ref().map(|idle| idle.in_flight.clone())
+    }
+
     /// Run `do_io` while connection processing should continue.
     fn poll_trigger_then<T>(
         mut self: Pin<&mut Self>,
@@ -177,20 +229,27 @@ impl<F: Future, I: AsyncWrite> CancellableIo<F, I> {
             None => return Poll::Ready(Err(gone())),
         };
 
+        if let Some(idle) = me.idle.as_mut() {
+            if idle.poll_expired(cx) {
+                self.project().io.set(None);
+                return Poll::Ready(Err(idle_timed_out()));
+            }
+        }
+
         loop {
             match me.state {
                 State::Active => {
                     if me.trigger.as_mut().poll(cx).is_ready() {
                         *me.state = State::Grace(Box::pin(sleep(*me.grace)));
                     } else {
-                        return do_io(io, cx);
+                        return Self::do_io_tracking_idle(me.idle, io, cx, do_io);
                     }
                 }
                 State::Grace(timer) => {
                     if timer.as_mut().poll(cx).is_ready() {
                         *me.state = State::Mercy(Box::pin(sleep(*me.mercy)));
                     } else {
-                        return do_io(io, cx);
+                        return Self::do_io_tracking_idle(me.idle, io, cx, do_io);
                     }
                 }
                 State::Mercy(timer) => {
@@ -211,6 +270,27 @@ impl<F: Future, I: AsyncWrite> CancellableIo<F, I> {
     }
 }
 
+impl<F, I> CancellableIo<F, I> {
+    /// Run `do_io`, resetting the idle timer, if any, if it makes progress.
+    fn do_io_tracking_idle<T>(
+        idle: &mut Option<Idle>,
+        io: Pin<&mut I>,
+        cx: &mut Context<'_>,
+        do_io: impl FnOnce(Pin<&mut I>, &mut Context<'_>) -> Poll<io::Result<T>>,
+    ) -> Poll<io::Result<T>> {
+        let result = do_io(io, cx);
+        if let (Some(idle), Poll::Ready(_)) = (idle, &result) {
+            idle.reset();
+        }
+
+        result
+    }
+}
+
+fn idle_timed_out() -> io::Error {
+    io::Error::new(io::ErrorKind::TimedOut, "Connection idle timeout expired")
+}
+
 fn time_out() -> io::Error {
     io::Error::new(io::ErrorKind::TimedOut, "Shutdown grace timed out")
 }
@@ -291,13 +371,19 @@ pin_project! {
         pub listener: L,
         pub grace: Duration,
         pub mercy: Duration,
+        pub idle_timeout: Option<Duration>,
     }
 }
 
 impl<F, L> CancellableListener<F, L> {
-    pub fn new(trigger: F, listener: L, grace: u64, mercy: u64) -> Self {
+    pub fn new(trigger: F, listener: L, grace: u64, mercy: u64, idle_timeout: u64) -> Self {
         let (grace, mercy) = (Duration::from_secs(grace), Duration::from_secs(mercy));
-        CancellableListener { trigger, listener, grace, mercy }
+        let idle_timeout = match idle_timeout {
+            0 => None,
+            n => Some(Duration::from_secs(n)),
+        };
+
+        CancellableListener { trigger, listener, grace, mercy, idle_timeout }
     }
 }
 
@@ -315,7 +401,8 @@ impl<L: Listener, F: Future + Clone> Listener for CancellableListener<F, L> {
         self.as_mut().project().listener
             .poll_accept(cx)
             .map(|res| res.map(|conn| {
-                CancellableIo::new(self.trigger.clone(), conn, self.grace, self.mercy)
+                let (grace, mercy, idle) = (self.grace, self.mercy, self.idle_timeout);
+                CancellableIo::new(self.trigger.clone(), conn, grace, mercy, idle)
             }))
     }
 }
@@ -151,7 +151,10 @@ pub type Result<T = Rocket<Build>, E = Rocket<Build>> = std::result::Result<T, E
 ///     into the incoming data. It may not, however, abort or respond directly
 ///     to the request; these issues are better handled via [request guards] or
 ///     via response callbacks. Any modifications to a request are persisted and
-///     can potentially alter how a request is routed.
+///     can potentially alter how a request is routed. In particular, a request
+///     callback may rewrite the request's URI via [`Request::set_uri()`], for
+///     instance to strip a deployment prefix; routing matches against the
+///     rewritten URI.
 ///
 ///   * **<a name="response">Response</a> (`on_response`)**
 ///
@@ -24,6 +24,11 @@ use crate::form::prelude::*;
 /// "a" and "c", the form _will_ parse as `Form<T>`. To parse strictly, use the
 /// [`Strict`](crate::form::Strict) form guard.
 ///
+/// Similarly, when a lenient form contains more than one value for a
+/// single-valued field, the first is used and the rest are discarded, while a
+/// strict form is rejected. Collections like `Vec<T>` collect every value. See
+/// [`Duplicates`](crate::form::Duplicates) for details.
+///
 /// # Usage
 ///
 /// This type can be used with any type that implements the `FromForm` trait.
@@ -199,6 +199,7 @@ impl<'v, T: FromFormField<'v>> FromFieldContext<'v, T> {
     fn should_push(&mut self) -> bool {
         self.pushes += 1;
         self.value.is_none()
+            || (!self.opts.strict && self.opts.duplicates == Duplicates::Last)
     }
 
     fn push(&mut self, name: NameView<'v>, result: Result<'v, T>) {
@@ -243,7 +244,7 @@ impl<'v, T: FromFormField<'v>> FromForm<'v> for T {
 
     fn finalize(ctxt: Self::Context) -> Result<'v, Self> {
         let mut errors = match ctxt.value {
-            Some(Ok(val)) if !ctxt.opts.strict || ctxt.pushes <= 1 => return Ok(val),
+            Some(Ok(val)) if !ctxt.opts.rejects_duplicates() || ctxt.pushes <= 1 => return Ok(val),
             Some(Ok(_)) => Errors::from(ErrorKind::Duplicate),
             Some(Err(errors)) => errors,
             None if !ctxt.opts.strict => match <T as FromFormField>::default() {
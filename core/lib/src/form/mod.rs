@@ -369,6 +369,7 @@ mod strict;
 mod lenient;
 mod parser;
 mod buffer;
+mod query;
 pub mod validate;
 pub mod name;
 pub mod error;
@@ -396,6 +397,7 @@ pub use form::*;
 pub use context::*;
 pub use strict::*;
 pub use lenient::*;
+pub use query::*;
 
 #[doc(hidden)]
 pub mod prelude {
@@ -5,13 +5,88 @@
 pub struct Options {
     /// Whether parsing should be strict (no extra parameters) or not.
     pub strict: bool,
+    /// How repeated values for a single-valued field are handled when parsing
+    /// leniently. Strict parsing always rejects them.
+    pub duplicates: Duplicates,
+    /// PRIVATE: This structure may grow (but never change otherwise) in a
+    /// non-breaking release. As such, constructing this structure should
+    /// _always_ be done using a public constant or update syntax:
+    ///
+    /// ```rust
+    /// use rocket::form::{Options, Duplicates};
+    ///
+    /// let opts = Options {
+    ///     duplicates: Duplicates::Last,
+    ///     ..Options::Lenient
+    /// };
+    /// ```
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+/// Policy for a single-valued field receiving more than one value.
+///
+/// A form like `a=1&a=2` contains two values for the field `a`. Collections
+/// such as `Vec<T>` and `HashMap<K, V>` collect every value, irrespective of
+/// the policy. A single-valued field, like a `usize`, can only hold one of
+/// them; this policy determines which, if any. When parsing
+/// [strictly](Options::strict), duplicates are always rejected.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::form::{FromForm, Options, Duplicates, QueryExt};
+/// use rocket::http::uri::Origin;
+///
+/// #[derive(FromForm)]
+/// struct Filter {
+///     page: usize,
+/// }
+///
+/// let uri = Origin::parse("/items?page=1&page=2").unwrap();
+/// let opts = Options { duplicates: Duplicates::Last, ..Options::Lenient };
+/// let filter: Filter = uri.query_as_with(opts).unwrap();
+/// assert_eq!(filter.page, 2);
+///
+/// let opts = Options { duplicates: Duplicates::Reject, ..Options::Lenient };
+/// assert!(uri.query_as_with::<Filter>(opts).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Duplicates {
+    /// Use the first value and ignore the rest. This is the default.
+    First,
+    /// Use the last value and ignore the rest.
+    Last,
+    /// Fail with [`ErrorKind::Duplicate`](crate::form::error::ErrorKind).
+    Reject,
+}
+
+impl Default for Duplicates {
+    /// Returns [`Duplicates::First`].
+    fn default() -> Self {
+        Duplicates::First
+    }
 }
 
 #[allow(non_upper_case_globals, dead_code)]
 impl Options {
-    /// `Options` with `strict` set to `false`.
-    pub const Lenient: Self = Options { strict: false };
+    /// `Options` with `strict` set to `false` and duplicates resolved with
+    /// [`Duplicates::First`].
+    pub const Lenient: Self = Options {
+        strict: false,
+        duplicates: Duplicates::First,
+        __non_exhaustive: (),
+    };
 
     /// `Options` with `strict` set to `true`.
-    pub const Strict: Self = Options { strict: true };
+    pub const Strict: Self = Options {
+        strict: true,
+        duplicates: Duplicates::First,
+        __non_exhaustive: (),
+    };
+
+    /// Whether a single-valued field with more than one value should fail.
+    pub(crate) fn rejects_duplicates(&self) -> bool {
+        self.strict || self.duplicates == Duplicates::Reject
+    }
 }
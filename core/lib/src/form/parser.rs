@@ -45,7 +45,14 @@ impl<'r, 'i> Parser<'r, 'i> {
     }
 
     async fn from_form(req: &'r Request<'i>, data: Data<'r>) -> Result<'r, Parser<'r, 'i>> {
-        let limit = req.limits().get("form").unwrap_or(Limits::FORM);
+        let limit = data.limit()
+            .or_else(|| req.limits().get("form"))
+            .unwrap_or(Limits::FORM);
+
+        if data.precheck_strict(limit).is_err() {
+            Err((None, Some(limit.as_u64())))?;
+        }
+
         let string = data.open(limit).into_string().await?;
         if !string.is_complete() {
             Err((None, Some(limit.as_u64())))?;
@@ -63,10 +70,14 @@ impl<'r, 'i> Parser<'r, 'i> {
             .param("boundary")
             .ok_or(multer::Error::NoBoundary)?;
 
-        let form_limit = req.limits()
-            .get("data-form")
+        let form_limit = data.limit()
+            .or_else(|| req.limits().get("data-form"))
             .unwrap_or(Limits::DATA_FORM);
 
+        if data.precheck_strict(form_limit).is_err() {
+            Err(multer::Error::StreamSizeExceeded { limit: form_limit.as_u64() })?;
+        }
+
         Ok(Parser::Multipart(MultipartParser {
             request: req,
             buffer: local_cache_once!(req, SharedStack::new()),
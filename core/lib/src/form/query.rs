@@ -0,0 +1,86 @@
+use crate::http::uri::{Absolute, Origin, Query};
+use crate::form::{FromForm, ValueField, Options, Result};
+
+/// Parses the query of a URI into a [`FromForm`] type.
+///
+/// This trait is implemented for [`Absolute`] and [`Origin`] URIs. It allows
+/// a query to be parsed into a `T: FromForm` the same way Rocket parses the
+/// query of an incoming request, but outside of a request: for example, to
+/// parse a URL extracted from a payload such as an OAuth callback URL. Fields
+/// are parsed [leniently](crate::form::Lenient), as by
+/// [`Form::parse_iter()`](crate::form::Form::parse_iter()), unless other
+/// [`Options`] are provided via [`QueryExt::query_as_with()`]. A URI without a
+/// query is parsed as if its query were empty.
+///
+/// Repeated keys, as in `?tag=a&tag=b`, are collected by collection fields such
+/// as `Vec<T>` and `HashMap<K, V>`. For single-valued fields, the first value
+/// is used by default; see [`Duplicates`](crate::form::Duplicates) for the
+/// other policies.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::form::{FromForm, QueryExt};
+/// use rocket::http::uri::Absolute;
+///
+/// #[derive(FromForm)]
+/// struct Callback<'r> {
+///     code: &'r str,
+///     state: Option<&'r str>,
+/// }
+///
+/// let uri = Absolute::parse("https://rocket.rs/callback?code=a%20b").unwrap();
+/// let callback: Callback<'_> = uri.query_as().unwrap();
+/// assert_eq!(callback.code, "a b");
+/// assert_eq!(callback.state, None);
+/// ```
+pub trait QueryExt {
+    /// Parses the query of `self` into a `T`.
+    ///
+    /// Returns an error if `T` fails to parse from the query's fields.
+    fn query_as<'a, T: FromForm<'a>>(&'a self) -> Result<'a, T> {
+        self.query_as_with(Options::Lenient)
+    }
+
+    /// Parses the query of `self` into a `T` with the options `opts`.
+    ///
+    /// Returns an error if `T` fails to parse from the query's fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::form::{FromForm, Options, QueryExt};
+    /// use rocket::http::uri::Origin;
+    ///
+    /// #[derive(FromForm)]
+    /// struct Search<'r> {
+    ///     q: &'r str,
+    /// }
+    ///
+    /// let uri = Origin::parse("/search?q=rocket&page=2").unwrap();
+    /// assert!(uri.query_as_with::<Search<'_>>(Options::Lenient).is_ok());
+    /// assert!(uri.query_as_with::<Search<'_>>(Options::Strict).is_err());
+    /// ```
+    fn query_as_with<'a, T: FromForm<'a>>(&'a self, opts: Options) -> Result<'a, T>;
+}
+
+fn parse<'a, T: FromForm<'a>>(query: Option<Query<'a>>, opts: Options) -> Result<'a, T> {
+    let mut ctxt = T::init(opts);
+    for field in query.iter().flat_map(|q| q.segments().map(ValueField::from)) {
+        T::push_value(&mut ctxt, field);
+    }
+
+    T::finalize(ctxt)
+}
+
+impl QueryExt for Absolute<'_> {
+    fn query_as_with<'a, T: FromForm<'a>>(&'a self, opts: Options) -> Result<'a, T> {
+        parse(self.query(), opts)
+    }
+}
+
+impl QueryExt for Origin<'_> {
+    fn query_as_with<'a, T: FromForm<'a>>(&'a self, opts: Options) -> Result<'a, T> {
+        parse(self.query(), opts)
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+
+use crate::http::ContentType;
+use crate::http::uncased::Uncased;
+
+/// Mapping from file extensions to the [`ContentType`] a [`NamedFile`] is
+/// served with.
+///
+/// [`NamedFile`] determines the `Content-Type` of a file from its extension.
+/// Extensions registered in the `ContentTypes` in [`Config::content_types`]
+/// are consulted first, followed by those recognized by
+/// [`ContentType::from_extension()`]. A file with any other extension, or with
+/// no extension, is served as `application/octet-stream`. Registering an
+/// extension thus both adds support for new extensions and overrides the
+/// built-in mappings. Extensions are matched case-insensitively.
+///
+/// # Defaults
+///
+/// The default `ContentTypes` is empty: only the built-in mappings are used.
+///
+/// # Configuration
+///
+/// `ContentTypes` is configured via a table, with each key an extension and
+/// each value a content type, parsed as [`ContentType::from_str()`] does:
+///
+/// ```toml
+/// [default.content_types]
+/// webmanifest = "application/manifest+json"
+/// md = "text/markdown; charset=utf-8"
+/// ```
+///
+/// Or directly with a `Config`:
+///
+/// ```rust
+/// use rocket::Config;
+/// use rocket::fs::ContentTypes;
+/// use rocket::http::ContentType;
+///
+/// let content_types = ContentTypes::new()
+///     .content_type("webmanifest", ContentType::new("application", "manifest+json"));
+///
+/// let config = Config { content_types, ..Config::default() };
+/// ```
+///
+/// [`NamedFile`]: crate::fs::NamedFile
+/// [`Config::content_types`]: crate::Config::content_types
+/// [`ContentType::from_str()`]: std::str::FromStr::from_str()
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ContentTypes {
+    #[serde(with = "figment::util::vec_tuple_map")]
+    types: Vec<(Uncased<'static>, ContentType)>
+}
+
+impl ContentTypes {
+    /// Construct a new `ContentTypes` with no extensions registered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::ContentTypes;
+    ///
+    /// let content_types = ContentTypes::new();
+    /// assert_eq!(content_types.get("webmanifest"), None);
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        ContentTypes { types: vec![] }
+    }
+
+    /// Registers `content_type` for the extension `ext`, consuming `self` and
+    /// returning a new `ContentTypes` with the mapping added or replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::ContentTypes;
+    /// use rocket::http::ContentType;
+    ///
+    /// let content_types = ContentTypes::new()
+    ///     .content_type("avif", ContentType::Binary)
+    ///     .content_type("AVIF", ContentType::AVIF);
+    ///
+    /// assert_eq!(content_types.get("avif"), Some(&ContentType::AVIF));
+    /// ```
+    pub fn content_type<S>(mut self, ext: S, content_type: ContentType) -> Self
+        where S: Into<Uncased<'static>>
+    {
+        let ext = ext.into();
+        match self.types.iter_mut().find(|(k, _)| *k == ext) {
+            Some((_, v)) => *v = content_type,
+            None => self.types.push((ext, content_type)),
+        }
+
+        self
+    }
+
+    /// Returns the content type registered for the extension `ext`, if any.
+    /// Built-in mappings are not considered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::ContentTypes;
+    /// use rocket::http::ContentType;
+    ///
+    /// let manifest = ContentType::new("application", "manifest+json");
+    /// let content_types = ContentTypes::new().content_type("webmanifest", manifest.clone());
+    ///
+    /// assert_eq!(content_types.get("WebManifest"), Some(&manifest));
+    /// assert_eq!(content_types.get("json"), None);
+    /// ```
+    pub fn get<S: AsRef<str>>(&self, ext: S) -> Option<&ContentType> {
+        let ext = ext.as_ref();
+        self.types.iter()
+            .find(|(k, _)| k == ext)
+            .map(|(_, content_type)| content_type)
+    }
+
+    /// Returns the content type for a file with the extension `ext`: the
+    /// registered content type if there is one, otherwise the one recognized
+    /// by [`ContentType::from_extension()`], if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::ContentTypes;
+    /// use rocket::http::ContentType;
+    ///
+    /// let content_types = ContentTypes::new().content_type("js", ContentType::Plain);
+    ///
+    /// assert_eq!(content_types.for_extension("js"), Some(ContentType::Plain));
+    /// assert_eq!(content_types.for_extension("json"), Some(ContentType::JSON));
+    /// assert_eq!(content_types.for_extension("foo"), None);
+    /// ```
+    pub fn for_extension<S: AsRef<str>>(&self, ext: S) -> Option<ContentType> {
+        let ext = ext.as_ref();
+        self.get(ext).cloned().or_else(|| ContentType::from_extension(ext))
+    }
+}
+
+impl fmt::Display for ContentTypes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.types.is_empty() {
+            return f.write_str("built-in");
+        }
+
+        for (i, (k, v)) in self.types.iter().enumerate() {
+            if i != 0 { f.write_str(", ")? }
+            write!(f, "{} = {}", k, v)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use std::io;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use crate::request::Request;
+use crate::response::{self, Responder, content::RawHtml};
+use crate::http::RawStr;
+use crate::fs::NamedFile;
+
+/// The [`Responder`] returned by [`NamedFile::open_dir()`]: either a file or
+/// a directory listing.
+#[derive(Debug)]
+pub enum DirResponse {
+    /// A file: the requested file or a directory's index file.
+    File(NamedFile),
+    /// A generated listing of the requested directory.
+    Listing(DirListing),
+}
+
+impl<'r> Responder<'r, 'static> for DirResponse {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        match self {
+            DirResponse::File(file) => file.respond_to(req),
+            DirResponse::Listing(listing) => listing.respond_to(req),
+        }
+    }
+}
+
+/// A [`Responder`] that sends a generated HTML listing of a directory.
+///
+/// A `DirListing` is returned by [`NamedFile::open_dir()`] when a directory
+/// without an index file is requested and [`Options::Listing`] is enabled. The
+/// listing links to every file and subdirectory in the directory, sorted by
+/// name, relative to the path of the request. Entries whose names are not
+/// valid UTF-8 are omitted, as are dotfiles unless [`Options::DotFiles`] is
+/// enabled. All names are HTML escaped.
+///
+/// [`NamedFile::open_dir()`]: crate::fs::NamedFile::open_dir()
+/// [`Options::Listing`]: crate::fs::Options::Listing
+/// [`Options::DotFiles`]: crate::fs::Options::DotFiles
+#[derive(Debug)]
+pub struct DirListing {
+    path: PathBuf,
+    entries: Vec<(String, bool)>,
+}
+
+impl DirListing {
+    /// Reads the entries of the directory at `path`.
+    pub(crate) async fn read(path: PathBuf, dotfiles: bool) -> io::Result<DirListing> {
+        let mut entries = vec![];
+        let mut dir = tokio::fs::read_dir(&path).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            let name = match entry.file_name().into_string() {
+                Ok(name) if dotfiles || !name.starts_with('.') => name,
+                _ => continue,
+            };
+
+            // Follow symlinks; skip broken ones.
+            if let Ok(metadata) = tokio::fs::metadata(entry.path()).await {
+                entries.push((name, metadata.is_dir()));
+            }
+        }
+
+        entries.sort();
+        Ok(DirListing { path, entries })
+    }
+
+    /// Retrieve the path of the listed directory.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fs::{NamedFile, Options, DirResponse};
+    ///
+    /// # async fn f() -> std::io::Result<()> {
+    /// let response = NamedFile::open_dir("static", "docs", Options::Listing).await?;
+    /// if let DirResponse::Listing(listing) = response {
+    ///     assert_eq!(listing.path(), std::path::Path::new("static/docs"));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+}
+
+impl<'r> Responder<'r, 'static> for DirListing {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let path = req.uri().path();
+        let base = path.as_str().trim_end_matches('/');
+        let title = format!("Index of {}/", RawStr::new(base).percent_decode_lossy());
+        let title = RawStr::new(&title).html_escape();
+
+        let mut html = String::new();
+        let _ = write!(html, "<!DOCTYPE html>\n<html>\n<head>\n\
+            <meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n\
+            <h1>{}</h1>\n<ul>\n", title, title);
+
+        for (name, is_dir) in &self.entries {
+            let slash = if *is_dir { "/" } else { "" };
+            // Both parts are valid URI paths and thus can't contain a `"`.
+            let href = format!("{}/{}{}", base, RawStr::new(name).percent_encode(), slash);
+            let _ = writeln!(html, "<li><a href=\"{}\">{}{}</a></li>",
+                href.replace('&', "&amp;"), RawStr::new(name).html_escape(), slash);
+        }
+
+        html.push_str("</ul>\n</body>\n</html>\n");
+        RawHtml(html).respond_to(req)
+    }
+}
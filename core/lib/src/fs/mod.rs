@@ -4,9 +4,13 @@ mod server;
 mod named_file;
 mod temp_file;
 mod file_name;
+mod dir_listing;
+mod content_types;
 
 pub use server::*;
 pub use named_file::*;
 pub use temp_file::*;
 pub use file_name::*;
+pub use dir_listing::*;
+pub use content_types::ContentTypes;
 pub use server::relative;
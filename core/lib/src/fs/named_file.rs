@@ -1,5 +1,6 @@
 use std::io;
-use std::path::{Path, PathBuf};
+use std::ffi::OsString;
+use std::path::{Component, Path, PathBuf};
 use std::ops::{Deref, DerefMut};
 
 use tokio::fs::File;
@@ -7,6 +8,7 @@ use tokio::fs::File;
 use crate::request::Request;
 use crate::response::{self, Responder};
 use crate::http::ContentType;
+use crate::fs::{DirListing, DirResponse, Options};
 
 /// A [`Responder`] that sends file data with a Content-Type based on its
 /// file extension.
@@ -37,7 +39,59 @@ use crate::http::ContentType;
 ///
 /// [`FileServer`]: crate::fs::FileServer
 #[derive(Debug)]
-pub struct NamedFile(PathBuf, File);
+pub struct NamedFile(PathBuf, File, Option<Coding>);
+
+/// The content coding of a file opened by [`NamedFile::open_precompressed()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Coding {
+    Identity,
+    Gzip,
+    Brotli,
+}
+
+impl Coding {
+    /// The precompressed codings, in order of preference.
+    const PRECOMPRESSED: [Coding; 2] = [Coding::Brotli, Coding::Gzip];
+
+    fn name(self) -> &'static str {
+        match self {
+            Coding::Identity => "identity",
+            Coding::Gzip => "gzip",
+            Coding::Brotli => "br",
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Coding::Identity => "",
+            Coding::Gzip => ".gz",
+            Coding::Brotli => ".br",
+        }
+    }
+
+    /// Returns the quality value `accept_encoding` assigns to `self`.
+    fn quality(self, accept_encoding: &str) -> f32 {
+        let mut wildcard = None;
+        for item in accept_encoding.split(',') {
+            let mut params = item.split(';').map(|p| p.trim());
+            let coding = params.next().unwrap_or("");
+            let q = params.filter_map(|p| p.strip_prefix("q="))
+                .find_map(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            let matches = coding.eq_ignore_ascii_case(self.name())
+                || (self == Coding::Gzip && coding.eq_ignore_ascii_case("x-gzip"));
+
+            if matches {
+                return q;
+            } else if coding == "*" {
+                wildcard = Some(q);
+            }
+        }
+
+        wildcard.unwrap_or(0.0)
+    }
+}
 
 impl NamedFile {
     /// Attempts to open a file in read-only mode.
@@ -65,7 +119,163 @@ impl NamedFile {
         // all of those `seek`s to determine the file size. But, what happens if
         // the file gets changed between now and then?
         let file = File::open(path.as_ref()).await?;
-        Ok(NamedFile(path.as_ref().to_path_buf(), file))
+        Ok(NamedFile(path.as_ref().to_path_buf(), file, None))
+    }
+
+    /// Attempts to open the file at `path` or, if the client accepts it, a
+    /// precompressed version of the file.
+    ///
+    /// `accept_encoding` is the value of the request's `Accept-Encoding`
+    /// header, if any. If it accepts `br`, the sibling file `${path}.br` is
+    /// opened if it exists. Otherwise, if it accepts `gzip`, `${path}.gz` is
+    /// opened if it exists. When both are accepted, the one with the higher
+    /// quality value is preferred, and `br` is preferred on ties. If neither
+    /// is accepted or exists, `path` itself is opened.
+    ///
+    /// The response sets `Content-Encoding` accordingly and its
+    /// `Content-Type` according to the extension of `path`, not that of the
+    /// precompressed file. It always sets `Vary: Accept-Encoding`. The file at
+    /// `path` must exist even if a precompressed file is served.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file at `path` cannot be opened, as with
+    /// [`NamedFile::open()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// use rocket::fs::NamedFile;
+    /// use rocket::request::{self, Request, FromRequest};
+    ///
+    /// struct AcceptEncoding<'r>(Option<&'r str>);
+    ///
+    /// #[rocket::async_trait]
+    /// impl<'r> FromRequest<'r> for AcceptEncoding<'r> {
+    ///     type Error = std::convert::Infallible;
+    ///
+    ///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+    ///         let value = req.headers().get_one("Accept-Encoding");
+    ///         request::Outcome::Success(AcceptEncoding(value))
+    ///     }
+    /// }
+    ///
+    /// #[get("/app.js")]
+    /// async fn app(encoding: AcceptEncoding<'_>) -> Option<NamedFile> {
+    ///     NamedFile::open_precompressed("static/app.js", encoding.0).await.ok()
+    /// }
+    /// ```
+    pub async fn open_precompressed<P>(
+        path: P,
+        accept_encoding: Option<&str>,
+    ) -> io::Result<NamedFile>
+        where P: AsRef<Path>
+    {
+        let file = NamedFile::open(path).await?;
+        Ok(file.precompressed(accept_encoding).await)
+    }
+
+    /// Replaces the opened file with the most preferred precompressed version
+    /// accepted by `accept_encoding` that exists, if any.
+    pub(crate) async fn precompressed(mut self, accept_encoding: Option<&str>) -> NamedFile {
+        let accept_encoding = accept_encoding.unwrap_or("");
+        let mut codings: Vec<_> = Coding::PRECOMPRESSED.iter()
+            .map(|&coding| (coding, coding.quality(accept_encoding)))
+            .filter(|&(_, q)| q > 0.0)
+            .collect();
+
+        // The sort is stable, so ties retain the order of preference.
+        codings.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        self.2 = Some(Coding::Identity);
+        for (coding, _) in codings {
+            let mut sidecar = OsString::from(self.0.as_os_str());
+            sidecar.push(coding.extension());
+            let sidecar = PathBuf::from(sidecar);
+            if !tokio::fs::metadata(&sidecar).await.map_or(false, |m| m.is_file()) {
+                continue;
+            }
+
+            if let Ok(file) = File::open(&sidecar).await {
+                self.1 = file;
+                self.2 = Some(coding);
+                break;
+            }
+        }
+
+        self
+    }
+
+    /// Opens the file or directory at `path` relative to the directory `root`,
+    /// serving directories according to `options`.
+    ///
+    /// `path` must be relative and must not contain `..` components: a path
+    /// that could escape `root` results in an error of kind
+    /// [`PermissionDenied`](io::ErrorKind::PermissionDenied). Unless
+    /// [`Options::DotFiles`] is set, a path with a component beginning with
+    /// `.` is treated as missing. If the resulting path is a file, it is
+    /// opened as with [`NamedFile::open()`]. If it is a directory:
+    ///
+    ///   * With [`Options::Index`], its `index.html` file is opened, if it
+    ///     exists.
+    ///   * Otherwise, with [`Options::Listing`], a [`DirListing`] of its
+    ///     entries is generated.
+    ///   * Otherwise, an error of kind [`NotFound`](io::ErrorKind::NotFound) is
+    ///     returned.
+    ///
+    /// Other options are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::get;
+    /// use std::path::PathBuf;
+    ///
+    /// use rocket::fs::{NamedFile, DirResponse, Options, relative};
+    ///
+    /// #[get("/files/<path..>")]
+    /// async fn files(path: PathBuf) -> Option<DirResponse> {
+    ///     let options = Options::Index | Options::Listing;
+    ///     NamedFile::open_dir(relative!("static"), path, options).await.ok()
+    /// }
+    /// ```
+    pub async fn open_dir<R, P>(root: R, path: P, options: Options) -> io::Result<DirResponse>
+        where R: AsRef<Path>, P: AsRef<Path>
+    {
+        let dotfiles = options.contains(Options::DotFiles);
+        let mut full_path = root.as_ref().to_path_buf();
+        for component in path.as_ref().components() {
+            match component {
+                Component::Normal(c) if !dotfiles && c.to_string_lossy().starts_with('.') => {
+                    let msg = "dotfiles are not allowed";
+                    return Err(io::Error::new(io::ErrorKind::NotFound, msg));
+                }
+                Component::Normal(c) => full_path.push(c),
+                Component::CurDir => continue,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                    let msg = "path escapes the root directory";
+                    return Err(io::Error::new(io::ErrorKind::PermissionDenied, msg));
+                }
+            }
+        }
+
+        if !tokio::fs::metadata(&full_path).await?.is_dir() {
+            return NamedFile::open(full_path).await.map(DirResponse::File);
+        }
+
+        if options.contains(Options::Index) {
+            let index = full_path.join("index.html");
+            if tokio::fs::metadata(&index).await.map_or(false, |m| m.is_file()) {
+                return NamedFile::open(index).await.map(DirResponse::File);
+            }
+        }
+
+        if options.contains(Options::Listing) {
+            return DirListing::read(full_path, dotfiles).await.map(DirResponse::Listing);
+        }
+
+        Err(io::Error::new(io::ErrorKind::NotFound, "directory has no index"))
     }
 
     /// Retrieve the underlying `File`.
@@ -142,16 +352,31 @@ impl NamedFile {
 }
 
 /// Streams the named file to the client. Sets or overrides the Content-Type in
-/// the response according to the file's extension if the extension is
-/// recognized. See [`ContentType::from_extension()`] for more information. If
+/// the response according to the file's extension as resolved by
+/// [`Config::content_types`]: a registered content type if there is one, or
+/// else the one recognized by [`ContentType::from_extension()`]. Files with
+/// any other extension, or none, are served as `application/octet-stream`. If
 /// you would like to stream a file with a different Content-Type than that
 /// implied by its extension, use a [`File`] directly.
+///
+/// [`Config::content_types`]: crate::Config::content_types
+///
+/// A file opened with [`NamedFile::open_precompressed()`] additionally sets
+/// `Vary: Accept-Encoding` and, if a precompressed file is served, the
+/// corresponding `Content-Encoding`.
 impl<'r> Responder<'r, 'static> for NamedFile {
     fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
         let mut response = self.1.respond_to(req)?;
-        if let Some(ext) = self.0.extension() {
-            if let Some(ct) = ContentType::from_extension(&ext.to_string_lossy()) {
-                response.set_header(ct);
+        let content_type = self.0.extension()
+            .and_then(|ext| req.rocket().config().content_types.for_extension(ext.to_string_lossy()))
+            .unwrap_or(ContentType::Binary);
+
+        response.set_header(content_type);
+
+        if let Some(coding) = self.2 {
+            response.adjoin_raw_header("Vary", "Accept-Encoding");
+            if coding != Coding::Identity {
+                response.set_raw_header("Content-Encoding", coding.name());
             }
         }
 
@@ -4,7 +4,7 @@ use crate::{Request, Data};
 use crate::http::{Method, uri::Segments, ext::IntoOwned};
 use crate::route::{Route, Handler, Outcome};
 use crate::response::Redirect;
-use crate::fs::NamedFile;
+use crate::fs::{NamedFile, DirResponse};
 
 /// Custom handler for serving static files.
 ///
@@ -179,6 +179,16 @@ impl FileServer {
         self.rank = rank;
         self
     }
+
+    /// Opens the file at `path`, precompressed if [`Options::Precompressed`]
+    /// is set.
+    async fn open_file(&self, req: &Request<'_>, path: &Path) -> std::io::Result<NamedFile> {
+        if self.options.contains(Options::Precompressed) {
+            NamedFile::open_precompressed(path, accept_encoding(req).as_deref()).await
+        } else {
+            NamedFile::open(path).await
+        }
+    }
 }
 
 impl From<FileServer> for Vec<Route> {
@@ -204,7 +214,7 @@ impl Handler for FileServer {
             };
 
             if segments.is_empty() {
-                let file = NamedFile::open(&self.root).await.ok();
+                let file = self.open_file(req, &self.root).await.ok();
                 return Outcome::from_or_forward(req, data, file);
             } else {
                 return Outcome::forward(data);
@@ -228,19 +238,33 @@ impl Handler for FileServer {
                     return Outcome::from_or_forward(req, data, Redirect::permanent(normal));
                 }
 
-                if !options.contains(Options::Index) {
-                    return Outcome::forward(data);
-                }
+                let precompressed = options.contains(Options::Precompressed);
+                let response = match NamedFile::open_dir(p, "", options).await {
+                    Ok(DirResponse::File(file)) if precompressed => {
+                        let encoding = accept_encoding(req);
+                        Some(DirResponse::File(file.precompressed(encoding.as_deref()).await))
+                    }
+                    response => response.ok(),
+                };
 
-                let index = NamedFile::open(p.join("index.html")).await.ok();
-                Outcome::from_or_forward(req, data, index)
+                Outcome::from_or_forward(req, data, response)
             },
-            Some(p) => Outcome::from_or_forward(req, data, NamedFile::open(p).await.ok()),
+            Some(p) => Outcome::from_or_forward(req, data, self.open_file(req, &p).await.ok()),
             None => Outcome::forward(data),
         }
     }
 }
 
+/// The combined value of all of `req`'s `Accept-Encoding` headers, if any.
+fn accept_encoding(req: &Request<'_>) -> Option<String> {
+    let values: Vec<_> = req.headers().get("Accept-Encoding").collect();
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(values.join(","))
+}
+
 /// A bitset representing configurable options for [`FileServer`].
 ///
 /// The valid options are:
@@ -250,8 +274,12 @@ impl Handler for FileServer {
 ///   * [`Options::Index`] - Render `index.html` pages for directory requests.
 ///   * [`Options::IndexFile`] - Allow serving a single file as the index.
 ///   * [`Options::Missing`] - Don't fail if the path to serve is missing.
+///   * [`Options::Listing`] - Render directory listings for directory
+///     requests without an index page.
 ///   * [`Options::NormalizeDirs`] - Redirect directories without a trailing
 ///     slash to ones with a trailing slash.
+///   * [`Options::Precompressed`] - Serve precompressed `.br` and `.gz`
+///     versions of files to clients that accept them.
 ///
 /// `Options` structures can be `or`d together to select two or more options.
 /// For instance, to request that both dot files and index pages be returned,
@@ -354,6 +382,31 @@ impl Options {
     /// prevent inevitable 404 errors. This option overrides that.
     pub const Missing: Options = Options(1 << 4);
 
+    /// Respond to requests for a directory without an index page with a
+    /// generated HTML listing of the directory's entries.
+    ///
+    /// When enabled, [`FileServer`] will respond to requests for a directory
+    /// for which [`Options::Index`] did not apply with a [`DirListing`].
+    /// Dotfiles are only listed if [`Options::DotFiles`] is also enabled.
+    ///
+    /// **Disabled by default.**
+    ///
+    /// [`DirListing`]: crate::fs::DirListing
+    pub const Listing: Options = Options(1 << 5);
+
+    /// Serve precompressed versions of files, if they exist, to clients that
+    /// accept them.
+    ///
+    /// When enabled, [`FileServer`] will respond to a request for the file
+    /// `${root}/app.js` from a client whose `Accept-Encoding` permits `br`
+    /// with `${root}/app.js.br`, if it exists, or, if it permits `gzip`, with
+    /// `${root}/app.js.gz`, if it exists, setting the `Content-Encoding`
+    /// accordingly. The `Content-Type` is determined by `app.js`, which must
+    /// exist. See [`NamedFile::open_precompressed()`] for details.
+    ///
+    /// **Disabled by default.**
+    pub const Precompressed: Options = Options(1 << 6);
+
     /// Returns `true` if `self` is a superset of `other`. In other words,
     /// returns `true` if all of the options in `other` are also in `self`.
     ///
@@ -3,7 +3,7 @@ use std::path::{PathBuf, Path};
 
 use crate::Request;
 use crate::http::{ContentType, Status};
-use crate::data::{self, FromData, Data, Capped, N, Limits};
+use crate::data::{self, FromData, Data, Capped, N, Limits, ByteUnit};
 use crate::form::{FromFormField, ValueField, DataField, error::Errors};
 use crate::outcome::IntoOutcome;
 use crate::fs::FileName;
@@ -438,18 +438,30 @@ impl<'v> TempFile<'v> {
         }
     }
 
+    /// The limit for a file of type `content_type`: the route's limit,
+    /// `route_limit`, or the `file/$ext` or `file` limit.
+    fn limit(
+        req: &Request<'_>,
+        route_limit: Option<ByteUnit>,
+        content_type: Option<&ContentType>,
+    ) -> ByteUnit {
+        let file_limit = content_type
+            .and_then(|ct| ct.extension())
+            .and_then(|ext| req.limits().find(&["file", ext.as_str()]));
+
+        route_limit
+            .or(file_limit)
+            .or_else(|| req.limits().get("file"))
+            .unwrap_or(Limits::FILE)
+    }
+
     async fn from<'a>(
         req: &Request<'_>,
         data: Data<'_>,
         file_name: Option<&'a FileName>,
         content_type: Option<ContentType>,
     ) -> io::Result<Capped<TempFile<'a>>> {
-        let limit = content_type.as_ref()
-            .and_then(|ct| ct.extension())
-            .and_then(|ext| req.limits().find(&["file", ext.as_str()]))
-            .or_else(|| req.limits().get("file"))
-            .unwrap_or(Limits::FILE);
-
+        let limit = TempFile::limit(req, data.limit(), content_type.as_ref());
         let temp_dir = req.rocket().config().temp_dir.relative();
         let file = task::spawn_blocking(move || NamedTempFile::new_in(temp_dir));
         let file = file.await;
@@ -505,4 +517,6 @@ impl<'r> FromData<'r> for Capped<TempFile<'_>> {
 }
 
 impl_strict_from_form_field_from_capped!(TempFile<'v>);
-impl_strict_from_data_from_capped!(TempFile<'_>);
+impl_strict_from_data_from_capped!(TempFile<'_>, |r: &Request<'_>, d: &Data<'_>| {
+    TempFile::limit(r, d.limit(), r.content_type())
+});
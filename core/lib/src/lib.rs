@@ -132,6 +132,7 @@ pub mod catcher;
 pub mod route;
 pub mod serde;
 pub mod shield;
+pub mod cors;
 pub mod fs;
 
 // Reexport of HTTP everything.
@@ -160,6 +161,119 @@ pub mod http {
     pub use crate::cookies::*;
 }
 
+pub mod listener {
+    //! Traits for serving Rocket over custom transports.
+    //!
+    //! A [`Listener`] yields [`Connection`]s. Rocket serves connections from a
+    //! listener passed to [`Rocket::launch_on()`](crate::Rocket::launch_on()).
+    //! Both traits are implemented for Tokio's TCP types, which is what
+    //! [`Rocket::launch()`](crate::Rocket::launch()) serves on by default.
+    //!
+    //! # Example
+    //!
+    //! Serve requests over in-memory duplex streams, then issue a request
+    //! through one:
+    //!
+    //! ```rust
+    //! # #[macro_use] extern crate rocket;
+    //! use std::io;
+    //! use std::net::SocketAddr;
+    //! use std::pin::Pin;
+    //! use std::task::{Context, Poll};
+    //!
+    //! use rocket::tokio::io::{self as tio, AsyncRead, AsyncWrite, DuplexStream, ReadBuf};
+    //! use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+    //! use rocket::tokio::sync::mpsc;
+    //! use rocket::listener::{Listener, Connection};
+    //!
+    //! struct Pipe(DuplexStream);
+    //!
+    //! struct PipeListener(mpsc::UnboundedReceiver<DuplexStream>);
+    //!
+    //! impl Listener for PipeListener {
+    //!     type Connection = Pipe;
+    //!
+    //!     fn local_addr(&self) -> Option<SocketAddr> {
+    //!         None
+    //!     }
+    //!
+    //!     fn poll_accept(
+    //!         mut self: Pin<&mut Self>,
+    //!         cx: &mut Context<'_>
+    //!     ) -> Poll<io::Result<Pipe>> {
+    //!         // Once every sender is gone, no connection ever arrives.
+    //!         match self.0.poll_recv(cx) {
+    //!             Poll::Ready(Some(stream)) => Poll::Ready(Ok(Pipe(stream))),
+    //!             _ => Poll::Pending,
+    //!         }
+    //!     }
+    //! }
+    //!
+    //! impl Connection for Pipe {
+    //!     fn peer_address(&self) -> Option<SocketAddr> {
+    //!         None
+    //!     }
+    //!
+    //!     fn enable_nodelay(&self) -> io::Result<()> {
+    //!         Ok(())
+    //!     }
+    //! }
+    //!
+    //! impl AsyncRead for Pipe {
+    //!     fn poll_read(
+    //!         mut self: Pin<&mut Self>,
+    //!         cx: &mut Context<'_>,
+    //!         buf: &mut ReadBuf<'_>
+    //!     ) -> Poll<io::Result<()>> {
+    //!         Pin::new(&mut self.0).poll_read(cx, buf)
+    //!     }
+    //! }
+    //!
+    //! impl AsyncWrite for Pipe {
+    //!     fn poll_write(
+    //!         mut self: Pin<&mut Self>,
+    //!         cx: &mut Context<'_>,
+    //!         buf: &[u8]
+    //!     ) -> Poll<io::Result<usize>> {
+    //!         Pin::new(&mut self.0).poll_write(cx, buf)
+    //!     }
+    //!
+    //!     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    //!         Pin::new(&mut self.0).poll_flush(cx)
+    //!     }
+    //!
+    //!     fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    //!         Pin::new(&mut self.0).poll_shutdown(cx)
+    //!     }
+    //! }
+    //!
+    //! #[get("/")]
+    //! fn index() -> &'static str {
+    //!     "Hello, pipe!"
+    //! }
+    //!
+    //! # rocket::async_test(async {
+    //! let (connect, accept) = mpsc::unbounded_channel();
+    //! let rocket = rocket::build().mount("/", routes![index]);
+    //! rocket::tokio::spawn(rocket.launch_on(PipeListener(accept)));
+    //!
+    //! let (mut client, server) = tio::duplex(4096);
+    //! connect.send(server).unwrap();
+    //! client.write_all(b"GET / HTTP/1.1\r\nHost: pipe\r\nConnection: close\r\n\r\n")
+    //!     .await
+    //!     .unwrap();
+    //!
+    //! let mut response = String::new();
+    //! client.read_to_string(&mut response).await.unwrap();
+    //! assert!(response.starts_with("HTTP/1.1 200 OK"));
+    //! assert!(response.ends_with("Hello, pipe!"));
+    //! # });
+    //! ```
+
+    #[doc(inline)]
+    pub use rocket_http::private::{Listener, Connection, Certificates};
+}
+
 #[cfg(feature = "mtls")]
 #[cfg_attr(nightly, doc(cfg(feature = "mtls")))]
 pub mod mtls;
@@ -265,7 +379,8 @@ pub fn async_main<R>(fut: impl std::future::Future<Output = R> + Send) -> R {
     // fairings with different config values, or values from non-Rocket configs.
     // See tokio-rs/tokio#3329 for a necessary solution in `tokio`.
     let c = Config::from(Config::figment());
-    async_run(fut, c.workers, c.max_blocking, c.shutdown.force, "rocket-worker-thread")
+    let name = format!("{}-thread", c.worker_name_prefix);
+    async_run(fut, c.workers, c.max_blocking, c.shutdown.force, &name)
 }
 
 /// Executes a `future` to completion on a new tokio-based Rocket async runtime.
@@ -91,6 +91,14 @@ impl<'c> LocalRequest<'c> {
             }
         }
 
+        // Reject requests with too many or too large headers, as the server
+        // would.
+        if let Err(status) = rocket.check_header_limits(&self.request) {
+            return LocalResponse::new(self.request, move |req| {
+                rocket.handle_error(status, req)
+            }).await
+        }
+
         // Actually dispatch the request.
         let mut data = Data::local(self.data);
         let token = rocket.preprocess_request(&mut self.request, &mut data).await;
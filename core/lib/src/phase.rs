@@ -113,5 +113,7 @@ phases! {
         pub(crate) config: Config,
         pub(crate) state: Container![Send + Sync],
         pub(crate) shutdown: Shutdown,
+        pub(crate) in_flight: std::sync::atomic::AtomicUsize,
+        pub(crate) body_budget: Option<crate::data::BodyBudget>,
     }
 }
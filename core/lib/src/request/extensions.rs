@@ -0,0 +1,133 @@
+use std::fmt;
+
+use state::Container;
+
+/// Typed, request-local storage explicitly populated during request handling.
+///
+/// Retrieved via [`Request::extensions()`](crate::Request::extensions()).
+/// Unlike [request-local cache](crate::Request::local_cache()), which lazily
+/// initializes a value on first access, values in `Extensions` are only ever
+/// present if they were explicitly [inserted](Extensions::insert()). This
+/// makes `Extensions` suitable for passing values from one request guard, or
+/// fairing, to later guards and the handler: a missing value means that the
+/// guard which inserts it did not run or did not succeed.
+///
+/// At most one value of any given type can be stored. Use wrapper types to
+/// store multiple values of the same type. Values are never removed or
+/// replaced, and `Extensions` is shared between a `Request` and its clones.
+///
+/// # Example
+///
+/// An authentication guard stashes decoded claims that a second guard reads:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::{self, Request, FromRequest};
+/// use rocket::outcome::{IntoOutcome, try_outcome};
+/// use rocket::http::Status;
+///
+/// struct Claims { user: String, admin: bool }
+///
+/// struct Authenticated;
+///
+/// #[rocket::async_trait]
+/// impl<'r> FromRequest<'r> for Authenticated {
+///     type Error = ();
+///
+///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+///         let claims = req.headers().get_one("Authorization")
+///             .map(|token| Claims { user: token.into(), admin: token == "root" });
+///
+///         let claims = try_outcome!(claims.or_forward(()));
+///         req.extensions().insert(claims);
+///         request::Outcome::Success(Authenticated)
+///     }
+/// }
+///
+/// struct Admin;
+///
+/// #[rocket::async_trait]
+/// impl<'r> FromRequest<'r> for Admin {
+///     type Error = ();
+///
+///     async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+///         req.extensions().get::<Claims>()
+///             .filter(|claims| claims.admin)
+///             .map(|_| Admin)
+///             .into_outcome((Status::Forbidden, ()))
+///     }
+/// }
+///
+/// #[get("/admin")]
+/// fn admin(_auth: Authenticated, _admin: Admin) { /* .. */ }
+/// ```
+pub struct Extensions {
+    container: Container![Send + Sync],
+}
+
+impl Extensions {
+    pub(crate) fn new() -> Extensions {
+        Extensions { container: <Container![Send + Sync]>::new() }
+    }
+
+    /// Inserts `value` if no value of type `T` is present. Returns `true` if
+    /// `value` was inserted and `false` if a value of type `T` was already
+    /// present, in which case `value` is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let extensions = request.extensions();
+    /// assert!(extensions.insert(10u32));
+    /// assert!(!extensions.insert(20u32));
+    /// assert_eq!(extensions.get::<u32>(), Some(&10));
+    /// ```
+    #[inline]
+    pub fn insert<T: Send + Sync + 'static>(&self, value: T) -> bool {
+        self.container.set(value)
+    }
+
+    /// Returns the value of type `T`, if one has been inserted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let extensions = request.extensions();
+    /// assert_eq!(extensions.get::<&str>(), None);
+    ///
+    /// extensions.insert("hello");
+    /// assert_eq!(extensions.get::<&str>(), Some(&"hello"));
+    /// ```
+    #[inline]
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.container.try_get()
+    }
+
+    /// Returns `true` if a value of type `T` has been inserted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let extensions = request.extensions();
+    /// assert!(!extensions.contains::<usize>());
+    ///
+    /// extensions.insert(1usize);
+    /// assert!(extensions.contains::<usize>());
+    /// ```
+    #[inline]
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.get::<T>().is_some()
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions").finish_non_exhaustive()
+    }
+}
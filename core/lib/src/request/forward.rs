@@ -0,0 +1,75 @@
+use std::fmt;
+
+use crate::Route;
+
+/// A route that was tried for a request but forwarded, and why.
+///
+/// Retrieved via [`Request::forwards()`](crate::Request::forwards()) when
+/// [`Config::trace_forwards`](crate::Config::trace_forwards) is enabled.
+///
+/// # Example
+///
+/// A `404` catcher that lists the routes which forwarded:
+///
+/// ```rust
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+///
+/// #[catch(404)]
+/// fn not_found(req: &Request) -> String {
+///     req.forwards().iter()
+///         .map(|forward| format!("{}: {}\n", forward.route().uri, forward.reason()))
+///         .collect()
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Forwarded<'r> {
+    route: &'r Route,
+    reason: ForwardReason,
+}
+
+/// The reason a route tried for a request forwarded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ForwardReason {
+    /// The route's path matched but its method did not. Such a route is never
+    /// tried; it is recorded only when no route handled the request.
+    MethodMismatch,
+    /// The request is a `HEAD` request and the matching `GET` route opted out
+    /// of `HEAD` autohandling.
+    AutoHeadDisabled,
+    /// A guard forwarded. The description names the guard and, if there is
+    /// one, the error it forwarded with.
+    Guard(String),
+    /// The handler forwarded without a guard having forwarded.
+    Handler,
+}
+
+impl<'r> Forwarded<'r> {
+    pub(crate) fn new(route: &'r Route, reason: ForwardReason) -> Self {
+        Forwarded { route, reason }
+    }
+
+    /// The route that forwarded.
+    #[inline(always)]
+    pub fn route(&self) -> &'r Route {
+        self.route
+    }
+
+    /// Why the route forwarded.
+    #[inline(always)]
+    pub fn reason(&self) -> &ForwardReason {
+        &self.reason
+    }
+}
+
+impl fmt::Display for ForwardReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForwardReason::MethodMismatch => "method mismatch".fmt(f),
+            ForwardReason::AutoHeadDisabled => "`HEAD` autohandling disabled".fmt(f),
+            ForwardReason::Guard(guard) => guard.fmt(f),
+            ForwardReason::Handler => "handler forwarded".fmt(f),
+        }
+    }
+}
@@ -3,6 +3,8 @@
 mod request;
 mod from_param;
 mod from_request;
+mod extensions;
+mod forward;
 
 #[cfg(test)]
 mod tests;
@@ -10,6 +12,8 @@ mod tests;
 pub use self::request::Request;
 pub use self::from_request::{FromRequest, Outcome};
 pub use self::from_param::{FromParam, FromSegments};
+pub use self::extensions::Extensions;
+pub use self::forward::{Forwarded, ForwardReason};
 
 #[doc(inline)]
 pub use crate::response::flash::FlashMessage;
@@ -2,22 +2,26 @@ use std::fmt;
 use std::ops::RangeFrom;
 use std::{future::Future, borrow::Cow, sync::Arc};
 use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
 
 use yansi::Paint;
 use state::{Container, Storage};
 use futures::future::BoxFuture;
+use parking_lot::Mutex;
 use atomic::{Atomic, Ordering};
 
 use crate::{Rocket, Route, Orbit};
-use crate::request::{FromParam, FromSegments, FromRequest, Outcome};
+use crate::request::{FromParam, FromSegments, FromRequest, Outcome, Extensions};
+use crate::request::{Forwarded, ForwardReason};
 use crate::form::{self, ValueField, FromForm};
 use crate::data::Limits;
 
-use crate::http::{hyper, Method, Header, HeaderMap};
+use crate::http::{hyper, Method, Header, HeaderMap, Status};
 use crate::http::{ContentType, Accept, MediaType, CookieJar, Cookie};
 use crate::http::uncased::UncasedStr;
 use crate::http::private::Certificates;
-use crate::http::uri::{fmt::Path, Origin, Segments, Host, Authority};
+use crate::http::uri::{fmt::Path, Origin, Absolute, Segments, Host, Authority};
 
 /// The type of an incoming web request.
 ///
@@ -45,11 +49,19 @@ pub(crate) struct ConnectionMeta {
 pub(crate) struct RequestState<'r> {
     pub rocket: &'r Rocket<Orbit>,
     pub route: Atomic<Option<&'r Route>>,
+    pub catcher_forward: Atomic<Option<Status>>,
     pub cookies: CookieJar<'r>,
     pub accept: Storage<Option<Accept>>,
     pub content_type: Storage<Option<ContentType>>,
     pub cache: Arc<Container![Send + Sync]>,
+    pub extensions: Arc<Extensions>,
+    pub forwards: Option<Mutex<Vec<Forwarded<'r>>>>,
+    pub body_aborted: Arc<AtomicBool>,
+    pub start: Atomic<Instant>,
+    pub timings: Mutex<Vec<(Cow<'static, str>, Duration)>>,
     pub host: Option<Host<'r>>,
+    pub version: Option<hyper::Version>,
+    pub raw_target: Option<Cow<'r, str>>,
 }
 
 impl Request<'_> {
@@ -69,11 +81,19 @@ impl RequestState<'_> {
         RequestState {
             rocket: self.rocket,
             route: Atomic::new(self.route.load(Ordering::Acquire)),
+            catcher_forward: Atomic::new(self.catcher_forward.load(Ordering::Acquire)),
             cookies: self.cookies.clone(),
             accept: self.accept.clone(),
             content_type: self.content_type.clone(),
             cache: self.cache.clone(),
+            extensions: self.extensions.clone(),
+            forwards: self.forwards.as_ref().map(|f| Mutex::new(f.lock().clone())),
+            body_aborted: self.body_aborted.clone(),
+            start: Atomic::new(self.start.load(Ordering::Acquire)),
+            timings: Mutex::new(self.timings.lock().clone()),
             host: self.host.clone(),
+            version: self.version,
+            raw_target: self.raw_target.clone(),
         }
     }
 }
@@ -97,11 +117,19 @@ impl<'r> Request<'r> {
             state: RequestState {
                 rocket,
                 route: Atomic::new(None),
+                catcher_forward: Atomic::new(None),
                 cookies: CookieJar::new(rocket.config()),
                 accept: Storage::new(),
                 content_type: Storage::new(),
                 cache: Arc::new(<Container![Send + Sync]>::new()),
+                extensions: Arc::new(Extensions::new()),
+                forwards: rocket.config().trace_forwards.then(|| Mutex::new(vec![])),
+                body_aborted: Arc::new(AtomicBool::new(false)),
+                start: Atomic::new(Instant::now()),
+                timings: Mutex::new(vec![]),
                 host: None,
+                version: None,
+                raw_target: None,
             }
         }
     }
@@ -161,6 +189,11 @@ impl<'r> Request<'r> {
 
     /// Set the URI in `self` to `uri`.
     ///
+    /// Requests are routed after [request fairings] run, so a request fairing
+    /// can use this method to rewrite the URI that routing matches against.
+    ///
+    /// [request fairings]: crate::fairing::Fairing#request
+    ///
     /// # Example
     ///
     /// ```rust
@@ -191,6 +224,13 @@ impl<'r> Request<'r> {
     /// component. Otherwise, this method returns the contents of the
     /// `:authority` pseudo-header request field.
     ///
+    /// If the request target is in absolute-form, as in `GET
+    /// http://rocket.rs/path HTTP/1.1`, which is typical of requests to
+    /// proxies, this method instead returns the authority of the target, as
+    /// required by RFC 7230 §5.4, irrespective of the `HOST` header. The
+    /// request's [`uri()`](Request::uri()), and thus routing, reflects only the
+    /// target's path and query.
+    ///
     /// Note that this method _only_ reflects the `HOST` header in the _initial_
     /// request and not any changes made thereafter. To change the value
     /// returned by this method, use [`Request::set_host()`].
@@ -301,6 +341,52 @@ impl<'r> Request<'r> {
         self.state.host = Some(host);
     }
 
+    /// Returns the HTTP version of the request as received, if it is known.
+    ///
+    /// The version is known for requests received from a client. It is not
+    /// known, and thus `None` is returned, for local requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// use rocket::http::hyper::Version;
+    ///
+    /// let is_http_1_0 = request.http_version() == Some(Version::HTTP_10);
+    /// # assert_eq!(request.http_version(), None);
+    /// ```
+    #[inline(always)]
+    pub fn http_version(&self) -> Option<hyper::Version> {
+        self.state.version
+    }
+
+    /// Returns the request target as received, if it is known.
+    ///
+    /// Unlike [`Request::uri()`], which is always in origin-form, the raw
+    /// target retains the form the client sent: `/path?query` for
+    /// origin-form, `http://host/path?query` for absolute-form, `*` for
+    /// asterisk-form, and `host:port` for the authority-form of `CONNECT`
+    /// requests. For HTTP/2 requests, the target is reconstructed from the
+    /// `:scheme`, `:authority`, and `:path` pseudo-headers and is thus in
+    /// absolute-form. The target is not known, and thus `None` is returned, for
+    /// local requests.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// if let Some(target) = request.raw_target() {
+    ///     println!("the client requested {}", target);
+    /// }
+    /// # assert_eq!(request.raw_target(), None);
+    /// ```
+    #[inline(always)]
+    pub fn raw_target(&self) -> Option<&str> {
+        self.state.raw_target.as_deref()
+    }
+
     /// Returns the raw address of the remote connection that initiated this
     /// request if the address is known. If the address is not known, `None` is
     /// returned.
@@ -414,6 +500,75 @@ impl<'r> Request<'r> {
         self.real_ip().or_else(|| self.remote().map(|r| r.ip()))
     }
 
+    /// Returns the fully-qualified, externally visible URL for `origin`, or
+    /// `None` if the host the request was made to is unknown.
+    ///
+    /// The scheme and authority of the URL are determined as follows:
+    ///
+    ///   1. If [`Config::public_url`] is set, its scheme and authority are
+    ///      used, and its path, if any, prefixes `origin`.
+    ///   2. Otherwise, if the request's remote address is in
+    ///      [`Config::trusted_proxies`], the first values of the
+    ///      `X-Forwarded-Proto` and `X-Forwarded-Host` headers are used, when
+    ///      present and valid, in place of those in (3).
+    ///   3. Otherwise, the scheme is `https` if TLS is enabled and `http`
+    ///      otherwise, and the authority is the request's [`host()`].
+    ///
+    /// Because the `Host` header is client controlled, prefer setting
+    /// `public_url` when the URL is sent to third parties, as in emails.
+    ///
+    /// [`Config::public_url`]: crate::Config::public_url
+    /// [`Config::trusted_proxies`]: crate::Config::trusted_proxies
+    /// [`host()`]: Request::host()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::uri;
+    /// use rocket::http::uri::Host;
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let mut req = c.get("/");
+    /// # let request = req.inner_mut();
+    /// // Without a host, no URL can be built.
+    /// assert!(request.url_for(uri!("/a/b")).is_none());
+    ///
+    /// request.set_host(Host::from(uri!("rocket.rs")));
+    /// let url = request.url_for(uri!("/a/b?c")).unwrap();
+    /// assert_eq!(url, uri!("http://rocket.rs/a/b?c"));
+    /// ```
+    pub fn url_for(&self, origin: Origin<'_>) -> Option<Absolute<'static>> {
+        fn first(value: &str) -> &str {
+            value.split(',').next().unwrap_or("").trim()
+        }
+
+        let config = self.rocket().config();
+        let base = match config.public_url {
+            Some(ref url) => {
+                let path = url.path().as_str().trim_end_matches('/');
+                format!("{}://{}{}", url.scheme(), url.authority()?, path)
+            }
+            None => {
+                let trusted = self.remote()
+                    .map_or(false, |r| config.trusted_proxies.contains(&r.ip()));
+
+                let header = |name| trusted.then(|| self.headers().get_one(name)).flatten();
+                let scheme = header("X-Forwarded-Proto")
+                    .map(first)
+                    .filter(|s| s.eq_ignore_ascii_case("http") || s.eq_ignore_ascii_case("https"))
+                    .unwrap_or(if config.tls_enabled() { "https" } else { "http" });
+
+                let host = header("X-Forwarded-Host")
+                    .and_then(|h| Host::parse(first(h)).ok())
+                    .or_else(|| self.host().cloned())?;
+
+                format!("{}://{}", scheme.to_ascii_lowercase(), host)
+            }
+        };
+
+        Absolute::parse_owned(format!("{}{}", base, origin)).ok()
+    }
+
     /// Returns a wrapped borrow to the cookies in `self`.
     ///
     /// [`CookieJar`] implements internal mutability, so this method allows you
@@ -662,6 +817,11 @@ impl<'r> Request<'r> {
     /// invoked. This method returns `None` _before_ routing has commenced; this
     /// includes during request fairing callbacks.
     ///
+    /// After routing, including during response fairing callbacks, this method
+    /// returns the route whose handler succeeded or failed, including when a
+    /// `HEAD` request is autohandled by a `GET` route. If every matching route
+    /// forwarded, or no route matched, this method returns `None`.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -669,6 +829,17 @@ impl<'r> Request<'r> {
     /// # let request = c.get("/");
     /// let route = request.route();
     /// ```
+    ///
+    /// Log the name of the route that handled each request:
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// let fairing = AdHoc::on_response("Route Logger", |req, res| Box::pin(async move {
+    ///     let name = req.route().and_then(|route| route.name.as_deref());
+    ///     println!("{} handled by {:?}: {}", req, name, res.status());
+    /// }));
+    /// ```
     #[inline(always)]
     pub fn route(&self) -> Option<&'r Route> {
         self.state.route.load(Ordering::Acquire)
@@ -695,6 +866,99 @@ impl<'r> Request<'r> {
         T::from_request(self)
     }
 
+    /// Returns the typed [`Extensions`] storage of `self`.
+    ///
+    /// Request guards and fairings can [`insert`](Extensions::insert()) values
+    /// that later guards and the handler [`get`](Extensions::get()). Unlike
+    /// [`Request::local_cache()`], a value is only present if it was inserted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// struct Claims { user: &'static str }
+    ///
+    /// assert!(request.extensions().get::<Claims>().is_none());
+    ///
+    /// request.extensions().insert(Claims { user: "bob" });
+    /// assert_eq!(request.extensions().get::<Claims>().unwrap().user, "bob");
+    /// ```
+    #[inline(always)]
+    pub fn extensions(&self) -> &Extensions {
+        &self.state.extensions
+    }
+
+    /// Returns the routes that were tried for `self` but forwarded, in the
+    /// order they were tried, along with the reason each forwarded.
+    ///
+    /// Forwards are only recorded when [`Config::trace_forwards`] is enabled,
+    /// as it is by default in debug. Otherwise, this method returns an empty
+    /// vector. If no route handled the request, the routes whose path matched
+    /// but whose method didn't are included last with a reason of
+    /// [`ForwardReason::MethodMismatch`]. This makes the trace especially
+    /// useful in a `404` or `405` catcher.
+    ///
+    /// [`Config::trace_forwards`]: crate::Config::trace_forwards
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// for forward in request.forwards() {
+    ///     println!("{} forwarded: {}", forward.route(), forward.reason());
+    /// }
+    /// ```
+    pub fn forwards(&self) -> Vec<Forwarded<'r>> {
+        self.state.forwards.as_ref()
+            .map(|forwards| forwards.lock().clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the time at which Rocket began handling `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let elapsed = request.start_time().elapsed();
+    /// ```
+    #[inline]
+    pub fn start_time(&self) -> Instant {
+        self.state.start.load(Ordering::Acquire)
+    }
+
+    /// Records that the step named `name` took `duration` while handling
+    /// `self`.
+    ///
+    /// When [`Config::server_timing`] is enabled, recorded timings are sent to
+    /// the client in a `Server-Timing` header along with a `total` timing for
+    /// the entire request. Otherwise, this method does nothing. `name` should
+    /// be a valid HTTP token: it should not contain whitespace, `;`, or `,`.
+    ///
+    /// [`Config::server_timing`]: crate::Config::server_timing
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Instant;
+    ///
+    /// # let c = rocket::local::blocking::Client::debug_with(vec![]).unwrap();
+    /// # let request = c.get("/");
+    /// let start = Instant::now();
+    /// // perform a database query...
+    /// request.record_timing("db", start.elapsed());
+    /// ```
+    pub fn record_timing<N>(&self, name: N, duration: Duration)
+        where N: Into<Cow<'static, str>>
+    {
+        if self.rocket().config().server_timing {
+            self.state.timings.lock().push((name.into(), duration));
+        }
+    }
+
     /// Retrieves the cached value for type `T` from the request-local cached
     /// state of `self`. If no such value has previously been cached for this
     /// request, `f` is called to produce the value which is subsequently
@@ -952,6 +1216,64 @@ impl<'r> Request<'r> {
         self.state.route.store(Some(route), Ordering::Release)
     }
 
+    /// Records that the current route forwarded because the guard described
+    /// by `guard` forwarded. Does nothing unless forwards are being traced.
+    /// Used by codegen.
+    #[inline]
+    pub fn _trace_guard_forward(&self, guard: fmt::Arguments<'_>) {
+        if let (Some(forwards), Some(route)) = (&self.state.forwards, self.route()) {
+            forwards.lock().push(Forwarded::new(route, ForwardReason::Guard(guard.to_string())));
+        }
+    }
+
+    /// Records that `route` forwarded for `reason`. Does nothing unless
+    /// forwards are being traced.
+    #[inline]
+    pub(crate) fn trace_forward(&self, route: &'r Route, reason: ForwardReason) {
+        if let Some(forwards) = &self.state.forwards {
+            forwards.lock().push(Forwarded::new(route, reason));
+        }
+    }
+
+    /// The number of forwards recorded so far, or `None` if forwards aren't
+    /// being traced.
+    #[inline]
+    pub(crate) fn traced_forwards(&self) -> Option<usize> {
+        self.state.forwards.as_ref().map(|forwards| forwards.lock().len())
+    }
+
+    /// Records that the running catcher forwarded to the catcher for `status`.
+    /// Used by [`catcher::Forward`](crate::catcher::Forward).
+    #[inline(always)]
+    pub(crate) fn set_catcher_forward(&self, status: Status) {
+        self.state.catcher_forward.store(Some(status), Ordering::Release)
+    }
+
+    /// Takes the status recorded by `set_catcher_forward()`, if any.
+    #[inline(always)]
+    pub(crate) fn take_catcher_forward(&self) -> Option<Status> {
+        self.state.catcher_forward.swap(None, Ordering::AcqRel)
+    }
+
+    /// Sets the time at which Rocket began handling `self` to now.
+    #[inline(always)]
+    pub(crate) fn set_start_time(&self) {
+        self.state.start.store(Instant::now(), Ordering::Release)
+    }
+
+    /// Returns the timings recorded via `record_timing()`.
+    #[inline]
+    pub(crate) fn timings(&self) -> Vec<(Cow<'static, str>, Duration)> {
+        self.state.timings.lock().clone()
+    }
+
+    /// Forget the route set by `set_route()`. Used when no route handled the
+    /// request.
+    #[inline(always)]
+    pub(crate) fn clear_route(&self) {
+        self.state.route.store(None, Ordering::Release)
+    }
+
     /// Set the method of `self`, even when `self` is a shared reference. Used
     /// during routing to override methods for re-routing.
     #[inline(always)]
@@ -959,6 +1281,13 @@ impl<'r> Request<'r> {
         self.method.store(method, Ordering::Release)
     }
 
+    /// Returns `true` if the connection was lost while the body of `self` was
+    /// being read, in which case no response can be delivered.
+    #[inline(always)]
+    pub(crate) fn body_aborted(&self) -> bool {
+        self.state.body_aborted.load(Ordering::Acquire)
+    }
+
     pub(crate) fn cookies_mut(&mut self) -> &mut CookieJar<'r> {
         &mut self.state.cookies
     }
@@ -979,8 +1308,21 @@ impl<'r> Request<'r> {
                 Method::Get
             });
 
+        // Refuse to parse a target whose path and query are too long.
+        let path_and_query = match hyper.uri.path_and_query() {
+            Some(uri) if uri.as_str().len() > rocket.config().max_uri_length => {
+                errors.push(Kind::UriTooLong(uri.as_str().len()));
+                None
+            }
+            Some(uri) => Some(uri),
+            None => {
+                errors.push(Kind::InvalidUri(&hyper.uri));
+                None
+            }
+        };
+
         // TODO: Keep around not just the path/query, but the rest, if there?
-        let uri = hyper.uri.path_and_query()
+        let uri = path_and_query
             .map(|uri| {
                 // In debug, make sure we agree with Hyper about URI validity.
                 // If we disagree, log a warning but continue anyway; if this is
@@ -995,10 +1337,7 @@ impl<'r> Request<'r> {
 
                 Origin::new(uri.path(), uri.query().map(Cow::Borrowed))
             })
-            .unwrap_or_else(|| {
-                errors.push(Kind::InvalidUri(&hyper.uri));
-                Origin::ROOT
-            });
+            .unwrap_or(Origin::ROOT);
 
         // Construct the request object; fill in metadata and headers next.
         let mut request = Request::new(rocket, method, uri);
@@ -1008,12 +1347,23 @@ impl<'r> Request<'r> {
             request.connection = connection;
         }
 
-        // Determine + set host. On HTTP < 2, use the `HOST` header. Otherwise,
-        // use the `:authority` pseudo-header which hyper makes part of the URI.
-        request.state.host = if hyper.version < hyper::Version::HTTP_2 {
-            hyper.headers.get("host").and_then(|h| Host::parse_bytes(h.as_bytes()).ok())
-        } else {
-            hyper.uri.host().map(|h| Host::new(Authority::new(None, h, hyper.uri.port_u16())))
+        // Remember the version and target as received. An origin-form target
+        // is borrowed as-is; other forms are reassembled by hyper's `Uri`.
+        request.state.version = Some(hyper.version);
+        request.state.raw_target = match hyper.uri.path_and_query() {
+            Some(target) if hyper.uri.scheme().is_none() => Some(target.as_str().into()),
+            _ => Some(hyper.uri.to_string().into()),
+        };
+
+        // Determine + set host. Use the authority hyper makes part of the URI:
+        // the `:authority` pseudo-header on HTTP/2, or the authority of an
+        // absolute-form target, which overrides `HOST`. Otherwise, on HTTP < 2,
+        // use the `HOST` header.
+        request.state.host = match hyper.uri.host() {
+            Some(h) => Some(Host::new(Authority::new(None, h, hyper.uri.port_u16()))),
+            None if hyper.version < hyper::Version::HTTP_2 => hyper.headers.get("host")
+                .and_then(|h| Host::parse_bytes(h.as_bytes()).ok()),
+            None => None,
         };
 
         // Set the request cookies, if they exist.
@@ -1059,9 +1409,22 @@ pub(crate) struct BadRequest<'r> {
     pub errors: Vec<Kind<'r>>,
 }
 
+impl BadRequest<'_> {
+    /// The status to respond with: `414` if the URI was too long, `400`
+    /// otherwise.
+    pub fn status(&self) -> Status {
+        if self.errors.iter().any(|e| matches!(e, Kind::UriTooLong(_))) {
+            Status::UriTooLong
+        } else {
+            Status::BadRequest
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum Kind<'r> {
     InvalidUri(&'r hyper::Uri),
+    UriTooLong(usize),
     BadMethod(&'r hyper::Method),
 }
 
@@ -1069,6 +1432,7 @@ impl fmt::Display for Kind<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Kind::InvalidUri(u) => write!(f, "invalid origin URI: {}", u),
+            Kind::UriTooLong(n) => write!(f, "URI of {} bytes exceeds limit", n),
             Kind::BadMethod(m) => write!(f, "invalid or unrecognized method: {}", m),
         }
     }
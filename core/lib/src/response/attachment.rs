@@ -0,0 +1,117 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::request::Request;
+use crate::response::{self, Responder};
+use crate::http::Header;
+
+/// Sets a `Content-Disposition: attachment` header so that clients download
+/// the response as a file named `name` instead of displaying it.
+///
+/// The header includes the name twice, as described in [RFC 6266]: as a
+/// quoted `filename` parameter, in which characters other than printable ASCII
+/// are replaced with `_`, for older clients, and as a percent-encoded UTF-8
+/// `filename*` parameter, which clients that support it prefer. For example,
+/// the name `résumé "final".pdf` results in:
+///
+/// ```text
+/// Content-Disposition: attachment; filename="r_sum_ \"final\".pdf"; filename*=UTF-8''r%C3%A9sum%C3%A9%20%22final%22.pdf
+/// ```
+///
+/// [RFC 6266]: https://tools.ietf.org/html/rfc6266
+///
+/// # Responder
+///
+/// The response is that of the wrapped responder `R` with the
+/// `Content-Disposition` header set, replacing any existing one. As such, an
+/// `Attachment` composes with other responders, such as
+/// [`NamedFile`](crate::fs::NamedFile), which sets the `Content-Type`.
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::get;
+/// use rocket::response::Attachment;
+/// use rocket::response::content::RawText;
+///
+/// #[get("/report")]
+/// fn report() -> Attachment<RawText<String>> {
+///     let csv = "name,count\nrocket,1\n".to_string();
+///     Attachment::new("report.csv", RawText(csv))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attachment<R> {
+    name: Cow<'static, str>,
+    responder: R,
+}
+
+impl<R> Attachment<R> {
+    /// Creates a new `Attachment` that responds with `responder`, to be
+    /// downloaded as a file named `name`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Attachment;
+    ///
+    /// let attachment = Attachment::new("hello.txt", "Hello, world!");
+    /// ```
+    pub fn new<N: Into<Cow<'static, str>>>(name: N, responder: R) -> Self {
+        Attachment { name: name.into(), responder }
+    }
+
+    /// Returns the name the attachment is downloaded as.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Attachment;
+    ///
+    /// let attachment = Attachment::new("hello.txt", "Hello, world!");
+    /// assert_eq!(attachment.name(), "hello.txt");
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value of the `Content-Disposition` header for this
+    /// attachment.
+    fn disposition(&self) -> String {
+        // RFC 5987 `attr-char`, which may appear unencoded in `filename*`.
+        fn is_attr_char(b: u8) -> bool {
+            b.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&b)
+        }
+
+        let mut value = String::from("attachment; filename=\"");
+        for c in self.name.chars() {
+            match c {
+                '"' | '\\' => { value.push('\\'); value.push(c); }
+                ' '..='~' => value.push(c),
+                _ => value.push('_'),
+            }
+        }
+
+        value.push_str("\"; filename*=UTF-8''");
+        for &b in self.name.as_bytes() {
+            if is_attr_char(b) {
+                value.push(b as char);
+            } else {
+                let _ = write!(value, "%{:02X}", b);
+            }
+        }
+
+        value
+    }
+}
+
+/// Sets the `Content-Disposition` header on the response of the wrapped
+/// responder.
+impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for Attachment<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let disposition = self.disposition();
+        let mut response = self.responder.respond_to(req)?;
+        response.set_header(Header::new("Content-Disposition", disposition));
+        Ok(response)
+    }
+}
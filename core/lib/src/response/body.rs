@@ -4,6 +4,8 @@ use std::pin::Pin;
 
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, ReadBuf};
 
+use crate::data::{ByteUnit, Capped, N};
+
 /// The body of a [`Response`].
 ///
 /// A `Body` is never created directly, but instead, through the following
@@ -57,7 +59,8 @@ use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, ReadBuf};
 ///
 /// The contents of a body, decoded, can be read through [`Body::to_bytes()`],
 /// [`Body::to_string()`], or directly though `Body`'s [`AsyncRead`]
-/// implementation.
+/// implementation. To read at most a fixed number of bytes, use
+/// [`Body::into_bytes()`] or [`Body::into_string()`].
 #[derive(Debug)]
 pub struct Body<'r> {
     /// The size of the body, if it is known.
@@ -66,6 +69,8 @@ pub struct Body<'r> {
     inner: Inner<'r>,
     /// The maximum chunk size.
     max_chunk: usize,
+    /// Whether to omit `Content-Length` even if the size is known.
+    force_chunked: bool,
 }
 
 /// A "trait alias" of sorts so we can use `AsyncRead + AsyncSeek` in `dyn`.
@@ -97,6 +102,7 @@ impl Default for Body<'_> {
             size: Some(0),
             inner: Inner::None,
             max_chunk: Body::DEFAULT_MAX_CHUNK,
+            force_chunked: false,
         }
     }
 }
@@ -114,6 +120,7 @@ impl<'r> Body<'r> {
             size: preset_size,
             inner: Inner::Seekable(Box::pin(body)),
             max_chunk: Body::DEFAULT_MAX_CHUNK,
+            force_chunked: false,
         }
     }
 
@@ -124,6 +131,7 @@ impl<'r> Body<'r> {
             size: None,
             inner: Inner::Unsized(Box::pin(body)),
             max_chunk: Body::DEFAULT_MAX_CHUNK,
+            force_chunked: false,
         }
     }
 
@@ -131,6 +139,10 @@ impl<'r> Body<'r> {
         self.max_chunk = max_chunk;
     }
 
+    pub(crate) fn set_force_chunked(&mut self, force_chunked: bool) {
+        self.force_chunked = force_chunked;
+    }
+
     pub(crate) fn strip(&mut self) {
         let body = std::mem::take(self);
         *self = match body.inner {
@@ -138,6 +150,7 @@ impl<'r> Body<'r> {
                 size: body.size,
                 inner: Inner::Phantom(b),
                 max_chunk: body.max_chunk,
+                force_chunked: body.force_chunked,
             },
             Inner::Unsized(_) | Inner::None => Body::default()
         };
@@ -250,6 +263,35 @@ impl<'r> Body<'r> {
         self.max_chunk
     }
 
+    /// Returns `true` if the body is to be written with chunked encoding even
+    /// if its size is known. See [`Response::set_force_chunked()`].
+    ///
+    /// [`Response::set_force_chunked()`]: crate::Response::set_force_chunked()
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::Response;
+    ///
+    /// let body = "Hello, world!";
+    /// let r = Response::build()
+    ///     .sized_body(body.len(), Cursor::new(body))
+    ///     .finalize();
+    ///
+    /// assert!(!r.body().is_force_chunked());
+    ///
+    /// let r = Response::build()
+    ///     .sized_body(body.len(), Cursor::new(body))
+    ///     .force_chunked()
+    ///     .finalize();
+    ///
+    /// assert!(r.body().is_force_chunked());
+    /// ```
+    pub fn is_force_chunked(&self) -> bool {
+        self.force_chunked
+    }
+
     /// Attempts to compute the body's size and returns it if the body is sized.
     ///
     /// If the size was preset (see [`Body::preset_size()`]), the value is
@@ -393,6 +435,84 @@ impl<'r> Body<'r> {
             io::Error::new(io::ErrorKind::InvalidData, e)
         })
     }
+
+    /// Reads at most `limit` bytes of `self` into a vector of bytes.
+    ///
+    /// If reading fails, returns `Err`. Otherwise, returns the bytes read as a
+    /// [`Capped`] whose [`is_complete()`](Capped::is_complete()) is `false` if
+    /// the body contained more than `limit` bytes, in which case the returned
+    /// bytes are truncated to `limit`. The remainder of the body is discarded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use rocket::data::ToByteUnit;
+    /// use rocket::response::Response;
+    ///
+    /// # let ok: io::Result<()> = rocket::async_test(async {
+    /// let mut r = Response::build()
+    ///     .streamed_body(io::Cursor::new(&[1, 2, 3, 11, 13, 17]))
+    ///     .finalize();
+    ///
+    /// let bytes = r.body_mut().take().into_bytes(4.bytes()).await?;
+    /// assert_eq!(bytes.value, &[1, 2, 3, 11]);
+    /// assert!(!bytes.is_complete());
+    /// # Ok(())
+    /// # });
+    /// # assert!(ok.is_ok());
+    /// ```
+    pub async fn into_bytes(mut self, limit: ByteUnit) -> io::Result<Capped<Vec<u8>>> {
+        let capacity = std::cmp::min(self.size.unwrap_or(0) as u64, limit.as_u64());
+        let mut vec = Vec::with_capacity(capacity as usize);
+        let mut reader = AsyncReadExt::take(&mut self, limit.as_u64());
+        let written = match reader.read_to_end(&mut vec).await {
+            Ok(n) => n as u64,
+            Err(e) => {
+                error_!("Error reading body: {:?}", e);
+                return Err(e);
+            }
+        };
+
+        let complete = written < limit.as_u64() || self.read(&mut [0u8; 1]).await? == 0;
+        Ok(Capped { value: vec, n: N { written, complete } })
+    }
+
+    /// Reads at most `limit` bytes of `self` into a string.
+    ///
+    /// If reading fails, or the bytes read contain invalid UTF-8 characters,
+    /// returns `Err`. Otherwise, returns the string as a [`Capped`] whose
+    /// [`is_complete()`](Capped::is_complete()) is `false` if the body
+    /// contained more than `limit` bytes. See [`Body::into_bytes()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use rocket::data::ToByteUnit;
+    /// use rocket::response::Response;
+    ///
+    /// # let ok: io::Result<()> = rocket::async_test(async {
+    /// let mut r = Response::build()
+    ///     .streamed_body(io::Cursor::new("Hello, Rocketeers!"))
+    ///     .finalize();
+    ///
+    /// let string = r.body_mut().take().into_string(1.kibibytes()).await?;
+    /// assert_eq!(string.value, "Hello, Rocketeers!");
+    /// assert!(string.is_complete());
+    /// # Ok(())
+    /// # });
+    /// # assert!(ok.is_ok());
+    /// ```
+    pub async fn into_string(self, limit: ByteUnit) -> io::Result<Capped<String>> {
+        let Capped { value, n } = self.into_bytes(limit).await?;
+        let value = String::from_utf8(value).map_err(|e| {
+            error_!("Body is invalid UTF-8: {}", e);
+            io::Error::new(io::ErrorKind::InvalidData, e)
+        })?;
+
+        Ok(Capped { value, n })
+    }
 }
 
 impl AsyncRead for Body<'_> {
@@ -0,0 +1,202 @@
+use std::io;
+use std::borrow::Cow;
+
+use crate::request::Request;
+use crate::response::{self, Responder, Response};
+use crate::http::{HeaderMap, Status};
+use crate::data::ByteUnit;
+use crate::http::hyper::body::Bytes;
+
+/// A [`Response`] whose body has been read into memory, as returned by
+/// [`Response::buffer()`].
+///
+/// Unlike a `Response`, which may stream its body, a `BufferedResponse` can be
+/// cloned cheaply and responded with any number of times: the body is shared
+/// between clones, not copied. This makes it suitable for in-memory response
+/// caches.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::io;
+/// use rocket::Response;
+/// use rocket::data::ToByteUnit;
+/// use rocket::http::Status;
+///
+/// # let o: io::Result<()> = rocket::async_test(async {
+/// let response = Response::build()
+///     .status(Status::Accepted)
+///     .streamed_body(io::Cursor::new("Hello, world!"))
+///     .finalize();
+///
+/// let buffered = response.buffer(1.kibibytes()).await?;
+/// assert_eq!(buffered.status(), Status::Accepted);
+/// assert_eq!(buffered.body(), Some(&b"Hello, world!"[..]));
+///
+/// let mut replay = buffered.clone().into_response();
+/// assert_eq!(replay.body_mut().to_string().await?, "Hello, world!");
+/// # Ok(())
+/// # });
+/// # assert!(o.is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct BufferedResponse {
+    status: Status,
+    reason: Option<Cow<'static, str>>,
+    headers: HeaderMap<'static>,
+    body: Option<Bytes>,
+    max_chunk_size: usize,
+    force_chunked: bool,
+}
+
+impl BufferedResponse {
+    pub(crate) async fn new(mut response: Response<'_>, limit: ByteUnit) -> io::Result<Self> {
+        if response.take_upgrade().is_some() {
+            let msg = "a response with a protocol upgrade cannot be buffered";
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+        }
+
+        let body = response.body_mut().take();
+        let (max_chunk_size, force_chunked) = (body.max_chunk_size(), body.is_force_chunked());
+        let body = if body.is_some() {
+            let bytes = body.into_bytes(limit).await?;
+            if !bytes.is_complete() {
+                let msg = format!("response body exceeds buffer limit of {}", limit);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+            }
+
+            Some(Bytes::from(bytes.into_inner()))
+        } else {
+            None
+        };
+
+        let mut headers = HeaderMap::new();
+        for header in response.headers().iter() {
+            headers.add_raw(header.name().as_str().to_owned(), header.value().to_owned());
+        }
+
+        Ok(BufferedResponse {
+            status: response.status(),
+            reason: response.reason_phrase().map(|r| Cow::Owned(r.to_owned())),
+            headers, body, max_chunk_size, force_chunked,
+        })
+    }
+
+    /// Returns the status of the buffered response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::io;
+    /// use rocket::Response;
+    /// use rocket::data::ToByteUnit;
+    /// use rocket::http::Status;
+    ///
+    /// # let o: io::Result<()> = rocket::async_test(async {
+    /// let response = Response::build().status(Status::NotFound).finalize();
+    /// let buffered = response.buffer(1.kibibytes()).await?;
+    /// assert_eq!(buffered.status(), Status::NotFound);
+    /// # Ok(())
+    /// # });
+    /// # assert!(o.is_ok());
+    /// ```
+    #[inline(always)]
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Returns the headers of the buffered response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::io;
+    /// use rocket::Response;
+    /// use rocket::data::ToByteUnit;
+    ///
+    /// # let o: io::Result<()> = rocket::async_test(async {
+    /// let response = Response::build().raw_header("X-Custom", "value").finalize();
+    /// let buffered = response.buffer(1.kibibytes()).await?;
+    /// assert_eq!(buffered.headers().get_one("X-Custom"), Some("value"));
+    /// # Ok(())
+    /// # });
+    /// # assert!(o.is_ok());
+    /// ```
+    #[inline(always)]
+    pub fn headers(&self) -> &HeaderMap<'static> {
+        &self.headers
+    }
+
+    /// Returns the body of the buffered response, or `None` if the response
+    /// had no body.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::io;
+    /// use rocket::Response;
+    /// use rocket::data::ToByteUnit;
+    ///
+    /// # let o: io::Result<()> = rocket::async_test(async {
+    /// let response = Response::build().finalize();
+    /// let buffered = response.buffer(1.kibibytes()).await?;
+    /// assert_eq!(buffered.body(), None);
+    /// # Ok(())
+    /// # });
+    /// # assert!(o.is_ok());
+    /// ```
+    #[inline(always)]
+    pub fn body(&self) -> Option<&[u8]> {
+        self.body.as_deref()
+    }
+
+    /// Converts `self` into a `Response` with the same status, headers, and
+    /// body. The body is sized and doesn't copy the buffered bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::io;
+    /// use rocket::Response;
+    /// use rocket::data::ToByteUnit;
+    ///
+    /// # let o: io::Result<()> = rocket::async_test(async {
+    /// let response = Response::build()
+    ///     .streamed_body(io::Cursor::new("Hi!"))
+    ///     .finalize();
+    ///
+    /// let buffered = response.buffer(1.kibibytes()).await?;
+    /// let replay = buffered.into_response();
+    /// assert_eq!(replay.body().preset_size(), Some(3));
+    /// # Ok(())
+    /// # });
+    /// # assert!(o.is_ok());
+    /// ```
+    pub fn into_response(self) -> Response<'static> {
+        let mut response = Response::new();
+        response.set_status(self.status);
+        if let Some(reason) = self.reason {
+            response.set_reason_phrase(reason);
+        }
+
+        for header in self.headers.into_iter() {
+            response.adjoin_header(header);
+        }
+
+        if let Some(body) = self.body {
+            response.set_sized_body(body.len(), io::Cursor::new(body));
+            response.set_max_chunk_size(self.max_chunk_size);
+            response.set_force_chunked(self.force_chunked);
+        }
+
+        response
+    }
+}
+
+/// Responds with the buffered status, headers, and body. To respond with the
+/// same `BufferedResponse` more than once, respond with clones of it.
+impl<'r> Responder<'r, 'static> for BufferedResponse {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Ok(self.into_response())
+    }
+}
@@ -77,6 +77,89 @@ ctrs! {
     RawJavaScript: JavaScript, "JavaScript", "application/javascript"
 }
 
+/// Sets the Content-Type of the response by sniffing the leading bytes of the
+/// body if the wrapped responder doesn't set a specific one.
+///
+/// The wrapped responder's Content-Type is replaced when it is missing or is
+/// the generic `application/octet-stream`, as it is for `Vec<u8>` and `&[u8]`.
+/// Sniffing recognizes the signatures of common formats, a subset of those in
+/// the [WHATWG MIME Sniffing Standard]:
+///
+///   * PNG, JPEG, GIF, and WebP images,
+///   * PDF documents,
+///   * and plain text, when the first 512 bytes are UTF-8 without any control
+///     characters other than whitespace.
+///
+/// If the body matches none of these, the response is left unchanged.
+/// Sniffing is opt-in because guessing a type can be surprising and, for
+/// user-provided content, unsafe: prefer setting an explicit type where one
+/// is known. Only in-memory bodies, those of responders that implement
+/// `AsRef<[u8]>`, can be sniffed.
+///
+/// [WHATWG MIME Sniffing Standard]: https://mimesniff.spec.whatwg.org/
+///
+/// # Example
+///
+/// ```rust
+/// # use rocket::get;
+/// use rocket::response::content::Sniffed;
+///
+/// #[get("/logo")]
+/// fn logo() -> Sniffed<Vec<u8>> {
+///     let png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n' /* .. */];
+///     Sniffed(png)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sniffed<R>(pub R);
+
+impl<'r, 'o: 'r, R: AsRef<[u8]> + Responder<'r, 'o>> Responder<'r, 'o> for Sniffed<R> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let sniffed = sniff(self.0.as_ref());
+        let mut response = self.0.respond_to(req)?;
+        if let Some(sniffed) = sniffed {
+            if response.content_type().map_or(true, |ct| ct == ContentType::Binary) {
+                response.set_header(sniffed);
+            }
+        }
+
+        Ok(response)
+    }
+}
+
+/// Guesses the Content-Type of `bytes` from its leading bytes.
+fn sniff(bytes: &[u8]) -> Option<ContentType> {
+    const SNIFF_BYTES: usize = 512;
+
+    let prefix = &bytes[..bytes.len().min(SNIFF_BYTES)];
+    match prefix {
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n', ..] => Some(ContentType::PNG),
+        [0xFF, 0xD8, 0xFF, ..] => Some(ContentType::JPEG),
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some(ContentType::GIF),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => {
+            Some(ContentType::WEBP)
+        }
+        [b'%', b'P', b'D', b'F', b'-', ..] => Some(ContentType::PDF),
+        _ if is_text(prefix, bytes.len() > SNIFF_BYTES) => Some(ContentType::Text),
+        _ => None,
+    }
+}
+
+/// Whether `prefix` is UTF-8 without binary data bytes. If `truncated`, a
+/// character cut off at the end of `prefix` is allowed.
+fn is_text(prefix: &[u8], truncated: bool) -> bool {
+    // The "binary data bytes" of the WHATWG MIME Sniffing Standard.
+    let is_binary = |b: &u8| matches!(b, 0x00..=0x08 | 0x0B | 0x0E..=0x1A | 0x1C..=0x1F);
+    if prefix.is_empty() || prefix.iter().any(is_binary) {
+        return false;
+    }
+
+    match std::str::from_utf8(prefix) {
+        Ok(_) => true,
+        Err(e) => truncated && e.error_len().is_none(),
+    }
+}
+
 impl<'r, 'o: 'r, R: Responder<'r, 'o>> Responder<'r, 'o> for (ContentType, R) {
     fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
         Response::build()
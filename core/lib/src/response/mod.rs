@@ -19,22 +19,27 @@ mod redirect;
 mod response;
 mod debug;
 mod body;
+mod buffered;
+mod attachment;
 
 pub(crate) mod flash;
 
 pub mod content;
 pub mod status;
 pub mod stream;
+pub mod upgrade;
 
 #[doc(hidden)]
 pub use rocket_codegen::Responder;
 
 pub use self::response::{Response, Builder};
 pub use self::body::Body;
+pub use self::buffered::BufferedResponse;
 pub use self::responder::Responder;
 pub use self::redirect::Redirect;
 pub use self::flash::Flash;
 pub use self::debug::Debug;
+pub use self::attachment::Attachment;
 
 /// Type alias for the `Result` of a [`Responder::respond_to()`] call.
 pub type Result<'r> = std::result::Result<Response<'r>, crate::http::Status>;
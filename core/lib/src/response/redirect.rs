@@ -46,7 +46,11 @@ use crate::http::Status;
 /// [`Origin`]: crate::http::uri::Origin
 /// [`uri!`]: ../macro.uri.html
 #[derive(Debug)]
-pub struct Redirect(Status, Option<Reference<'static>>);
+pub struct Redirect {
+    status: Status,
+    uri: Option<Reference<'static>>,
+    check_loop: bool,
+}
 
 impl Redirect {
     /// Construct a temporary "see other" (303) redirect response. This is the
@@ -64,7 +68,7 @@ impl Redirect {
     /// let redirect = Redirect::to(uri!("https://domain.com#foo"));
     /// ```
     pub fn to<U: TryInto<Reference<'static>>>(uri: U) -> Redirect {
-        Redirect(Status::SeeOther, uri.try_into().ok())
+        Redirect { status: Status::SeeOther, uri: uri.try_into().ok(), check_loop: false }
     }
 
     /// Construct a "temporary" (307) redirect response. This response instructs
@@ -84,7 +88,7 @@ impl Redirect {
     /// let redirect = Redirect::temporary(format!("some-{}-thing", "crazy"));
     /// ```
     pub fn temporary<U: TryInto<Reference<'static>>>(uri: U) -> Redirect {
-        Redirect(Status::TemporaryRedirect, uri.try_into().ok())
+        Redirect { status: Status::TemporaryRedirect, uri: uri.try_into().ok(), check_loop: false }
     }
 
    /// Construct a "permanent" (308) redirect response. This redirect must only
@@ -104,7 +108,7 @@ impl Redirect {
    /// let redirect = Redirect::permanent(format!("some-{}-thing", "crazy"));
    /// ```
    pub fn permanent<U: TryInto<Reference<'static>>>(uri: U) -> Redirect {
-       Redirect(Status::PermanentRedirect, uri.try_into().ok())
+       Redirect { status: Status::PermanentRedirect, uri: uri.try_into().ok(), check_loop: false }
    }
 
    /// Construct a temporary "found" (302) redirect response. This response
@@ -124,7 +128,7 @@ impl Redirect {
    /// let redirect = Redirect::found(format!("some-{}-thing", "crazy"));
    /// ```
    pub fn found<U: TryInto<Reference<'static>>>(uri: U) -> Redirect {
-       Redirect(Status::Found, uri.try_into().ok())
+       Redirect { status: Status::Found, uri: uri.try_into().ok(), check_loop: false }
    }
 
    /// Construct a permanent "moved" (301) redirect response. This response
@@ -142,19 +146,86 @@ impl Redirect {
    /// let redirect = Redirect::moved(format!("some-{}-thing", "crazy"));
    /// ```
    pub fn moved<U: TryInto<Reference<'static>>>(uri: U) -> Redirect {
-       Redirect(Status::MovedPermanently, uri.try_into().ok())
+       Redirect { status: Status::MovedPermanently, uri: uri.try_into().ok(), check_loop: false }
+   }
+
+   /// Like [`Redirect::to()`] but checks that the redirect does not point back
+   /// to the URI of the request being responded to.
+   ///
+   /// The check happens when the redirect is used as a response. A redirect
+   /// points back to the request when its path, query, and, if present, scheme
+   /// and authority are identical to those of the request; the fragment is
+   /// ignored. An empty path, as in `?q` or `#top`, refers to the request's
+   /// path. If the redirect would loop, a warning is logged and the responder
+   /// fails with `Status::LoopDetected` (508). Otherwise, the response is the
+   /// same as that of [`Redirect::to()`].
+   ///
+   /// This is useful when the redirect target is computed from user input.
+   ///
+   /// # Examples
+   ///
+   /// ```rust
+   /// # #[macro_use] extern crate rocket;
+   /// use rocket::response::Redirect;
+   ///
+   /// #[get("/go?<to>")]
+   /// fn go(to: String) -> Redirect {
+   ///     Redirect::to_checked(to)
+   /// }
+   /// ```
+   pub fn to_checked<U: TryInto<Reference<'static>>>(uri: U) -> Redirect {
+       Redirect { check_loop: true, ..Redirect::to(uri) }
    }
 }
 
+/// Returns `true` if `uri` resolves to the URI of `req`, ignoring fragments.
+fn is_self_redirect(req: &Request<'_>, uri: &Reference<'_>) -> bool {
+    if let Some(scheme) = uri.scheme() {
+        let req_scheme = match req.rocket().config().tls_enabled() {
+            true => "https",
+            false => "http"
+        };
+
+        if !scheme.eq_ignore_ascii_case(req_scheme) {
+            return false;
+        }
+    }
+
+    if let Some(authority) = uri.authority() {
+        let same_host = req.host().map_or(false, |host| {
+            host.domain() == authority.host() && host.port() == authority.port()
+        });
+
+        if !same_host {
+            return false;
+        }
+    }
+
+    let query = uri.query().map(|q| q.as_str());
+    let req_query = req.uri().query().map(|q| q.as_str());
+    let path = match uri.path().as_str() {
+        "" if uri.authority().is_some() => "/",
+        "" => return query.is_none() || query == req_query,
+        path => path,
+    };
+
+    path == req.uri().path().as_str() && query == req_query
+}
+
 /// Constructs a response with the appropriate status code and the given URL in
 /// the `Location` header field. The body of the response is empty. If the URI
 /// value used to create the `Responder` is an invalid URI, an error of
 /// `Status::InternalServerError` is returned.
 impl<'r> Responder<'r, 'static> for Redirect {
-    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
-        if let Some(uri) = self.1 {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        if let Some(uri) = self.uri {
+            if self.check_loop && is_self_redirect(req, &uri) {
+                warn_!("Refusing to redirect {} back to itself.", req.uri());
+                return Err(Status::LoopDetected);
+            }
+
             Response::build()
-                .status(self.0)
+                .status(self.status)
                 .raw_header("Location", uri.to_string())
                 .ok()
         } else {
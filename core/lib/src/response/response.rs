@@ -1,10 +1,17 @@
-use std::{fmt, str};
+use std::{fmt, io, str};
 use std::borrow::Cow;
 
 use tokio::io::{AsyncRead, AsyncSeek};
+use tokio_util::io::StreamReader;
+use futures::stream::{BoxStream, Stream, TryStreamExt};
+
+use crate::ext::AsyncReadExt;
+use crate::http::hyper::body::Bytes;
 
 use crate::http::{Header, HeaderMap, Status, ContentType, Cookie};
-use crate::response::Body;
+use crate::response::{Body, BufferedResponse};
+use crate::data::ByteUnit;
+use crate::response::upgrade::UpgradeHandler;
 
 /// Builder for the [`Response`] type.
 ///
@@ -106,6 +113,28 @@ impl<'r> Builder<'r> {
         self
     }
 
+    /// Sets the custom reason phrase of the `Response` being built to
+    /// `reason`. See [`Response::set_reason_phrase()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::http::Status;
+    ///
+    /// let response = Response::build()
+    ///     .status(Status::UnprocessableEntity)
+    ///     .reason_phrase("Validation Failed")
+    ///     .finalize();
+    /// ```
+    #[inline(always)]
+    pub fn reason_phrase<R>(&mut self, reason: R) -> &mut Builder<'r>
+        where R: Into<Cow<'static, str>>
+    {
+        self.response.set_reason_phrase(reason);
+        self
+    }
+
     /// Adds `header` to the `Response`, replacing any header with the same name
     /// that already exists in the response. If multiple headers with
     /// the same name exist, they are all removed, and only the new header and
@@ -282,6 +311,29 @@ impl<'r> Builder<'r> {
         self
     }
 
+    /// Writes the body, if any, with chunked encoding even if its size is
+    /// known.
+    ///
+    /// See [`Response::set_force_chunked()`] for notes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::Response;
+    ///
+    /// let body = "Hello, world!";
+    /// let response = Response::build()
+    ///     .sized_body(body.len(), Cursor::new(body))
+    ///     .force_chunked()
+    ///     .finalize();
+    /// ```
+    #[inline(always)]
+    pub fn force_chunked(&mut self) -> &mut Builder<'r> {
+        self.response.set_force_chunked(true);
+        self
+    }
+
     /// Merges the `other` `Response` into `self` by setting any fields in
     /// `self` to the corresponding value in `other` if they are set in `other`.
     /// Fields in `self` are unchanged if they are not set in `other`. If a
@@ -411,8 +463,10 @@ impl<'r> Builder<'r> {
 #[derive(Default)]
 pub struct Response<'r> {
     status: Option<Status>,
+    reason: Option<Cow<'static, str>>,
     headers: HeaderMap<'r>,
     body: Body<'r>,
+    upgrade: Option<UpgradeHandler>,
 }
 
 impl<'r> Response<'r> {
@@ -490,6 +544,10 @@ impl<'r> Response<'r> {
 
     /// Sets the status of `self` to `status`.
     ///
+    /// A custom [reason phrase](Response::set_reason_phrase()) describes the
+    /// status it was set for, so it is cleared if `status` differs from the
+    /// current status.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -499,12 +557,63 @@ impl<'r> Response<'r> {
     /// let mut response = Response::new();
     /// response.set_status(Status::ImATeapot);
     /// assert_eq!(response.status(), Status::ImATeapot);
+    ///
+    /// response.set_reason_phrase("Short and Stout");
+    /// response.set_status(Status::InternalServerError);
+    /// assert_eq!(response.reason_phrase(), None);
     /// ```
     #[inline(always)]
     pub fn set_status(&mut self, status: Status) {
+        if self.status != Some(status) {
+            self.reason = None;
+        }
+
         self.status = Some(status);
     }
 
+    /// Returns the custom reason phrase of `self`, if one has been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    ///
+    /// let mut response = Response::new();
+    /// assert_eq!(response.reason_phrase(), None);
+    ///
+    /// response.set_reason_phrase("Validation Failed");
+    /// assert_eq!(response.reason_phrase(), Some("Validation Failed"));
+    /// ```
+    #[inline(always)]
+    pub fn reason_phrase(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// Sets the reason phrase of `self` to `reason`, to be written in place of
+    /// the status's canonical reason phrase.
+    ///
+    /// Reason phrases exist only in HTTP/1: a custom reason phrase is written
+    /// in the status line of HTTP/1 responses and ignored for HTTP/2
+    /// responses. A reason phrase containing characters not allowed in a
+    /// status line, such as `\r` or `\n`, is also ignored, and the canonical
+    /// reason phrase is written instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::http::Status;
+    ///
+    /// let mut response = Response::new();
+    /// response.set_status(Status::UnprocessableEntity);
+    /// response.set_reason_phrase("Validation Failed");
+    /// assert_eq!(response.reason_phrase(), Some("Validation Failed"));
+    /// ```
+    #[inline(always)]
+    pub fn set_reason_phrase<R: Into<Cow<'static, str>>>(&mut self, reason: R) {
+        self.reason = Some(reason.into());
+    }
+
     /// Returns the Content-Type header of `self`. If the header is not present
     /// or is malformed, returns `None`.
     ///
@@ -837,6 +946,133 @@ impl<'r> Response<'r> {
         self.body_mut().set_max_chunk_size(size);
     }
 
+    /// Sets whether to write the body with chunked encoding even if its size
+    /// is known.
+    ///
+    /// By default, a body of known size is written with a `Content-Length`
+    /// header. When `chunked` is `true`, `Content-Length` is omitted and, for
+    /// HTTP/1.1, the body is written with `Transfer-Encoding: chunked`. Like
+    /// the max chunk size, this is a property of the body and is thus reset
+    /// whenever a body is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use rocket::Response;
+    ///
+    /// let body = "Hello, world!";
+    /// let mut response = Response::new();
+    /// response.set_sized_body(body.len(), Cursor::new(body));
+    /// response.set_force_chunked(true);
+    /// assert!(response.body().is_force_chunked());
+    /// ```
+    #[inline(always)]
+    pub fn set_force_chunked(&mut self, chunked: bool) {
+        self.body_mut().set_force_chunked(chunked);
+    }
+
+    /// Transforms the body of `self`, as it's streamed, with `f`.
+    ///
+    /// `f` is called immediately with the current body as a stream of chunks
+    /// and returns the stream that becomes the new body. The body is not read
+    /// until the response is written, so `f` is a means, say for a response
+    /// fairing, to inspect or rewrite a body of any size without buffering
+    /// it. If `self` has no body, `f` receives an empty stream.
+    ///
+    /// As the transform may change the size of the body, the new body is
+    /// unsized and any `Content-Length` header is removed. The body's max
+    /// chunk size is preserved.
+    ///
+    /// # Example
+    ///
+    /// Append a footer to the body:
+    ///
+    /// ```rust
+    /// # use std::io;
+    /// use rocket::Response;
+    /// use rocket::futures::stream::{self, StreamExt};
+    /// use rocket::http::hyper::body::Bytes;
+    ///
+    /// # let o: io::Result<()> = rocket::async_test(async {
+    /// let mut response = Response::build()
+    ///     .sized_body(5, io::Cursor::new("Hello"))
+    ///     .finalize();
+    ///
+    /// response.map_body_stream(|body| {
+    ///     body.chain(stream::once(async { Ok(Bytes::from(", world!")) }))
+    /// });
+    ///
+    /// assert_eq!(response.body().preset_size(), None);
+    /// assert_eq!(response.body_mut().to_string().await?, "Hello, world!");
+    /// # Ok(())
+    /// # });
+    /// # assert!(o.is_ok());
+    /// ```
+    pub fn map_body_stream<F, S, B>(&mut self, f: F)
+        where F: FnOnce(BoxStream<'r, io::Result<Bytes>>) -> S,
+              S: Stream<Item = io::Result<B>> + Send + 'r,
+              B: Into<Bytes> + 'r,
+    {
+        let body = self.body.take();
+        let max_chunk = body.max_chunk_size();
+        let stream = f(Box::pin(body.into_bytes_stream(max_chunk)));
+        self.set_streamed_body(StreamReader::new(stream.map_ok(Into::into)));
+        self.set_max_chunk_size(max_chunk);
+        self.remove_header("Content-Length");
+    }
+
+    /// Reads the body of `self`, up to `limit` bytes, into memory and returns
+    /// a [`BufferedResponse`] with the status, headers, and body of `self`.
+    ///
+    /// A `BufferedResponse` can be cloned and responded with repeatedly, as
+    /// is needed to cache a response. Streamed bodies are read in full, so
+    /// `limit` should be chosen with care.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading the body fails, if the body is larger than
+    /// `limit`, or if `self` upgrades the connection to another protocol.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::io;
+    /// use rocket::Response;
+    /// use rocket::data::ToByteUnit;
+    ///
+    /// # let o: io::Result<()> = rocket::async_test(async {
+    /// let response = Response::build()
+    ///     .streamed_body(io::Cursor::new("Hello, world!"))
+    ///     .finalize();
+    ///
+    /// let buffered = response.buffer(1.kibibytes()).await?;
+    /// assert_eq!(buffered.body(), Some(&b"Hello, world!"[..]));
+    ///
+    /// let response = Response::build()
+    ///     .streamed_body(io::Cursor::new("Hello, world!"))
+    ///     .finalize();
+    ///
+    /// assert!(response.buffer(4.bytes()).await.is_err());
+    /// # Ok(())
+    /// # });
+    /// # assert!(o.is_ok());
+    /// ```
+    pub async fn buffer(self, limit: ByteUnit) -> io::Result<BufferedResponse> {
+        BufferedResponse::new(self, limit).await
+    }
+
+    /// Sets the handler to run on the raw connection once this response, a
+    /// `101 Switching Protocols`, has been written.
+    pub(crate) fn set_upgrade(&mut self, handler: UpgradeHandler) {
+        self.upgrade = Some(handler);
+    }
+
+    /// Takes the upgrade handler, if any, out of `self`.
+    pub(crate) fn take_upgrade(&mut self) -> Option<UpgradeHandler> {
+        self.upgrade.take()
+    }
+
     /// Replaces this response's status and body with that of `other`, if they
     /// exist in `other`. Any headers that exist in `other` replace the ones in
     /// `self`. Any in `self` that aren't in `other` remain in `self`.
@@ -870,13 +1106,21 @@ impl<'r> Response<'r> {
     /// ```
     pub fn merge(&mut self, other: Response<'r>) {
         if let Some(status) = other.status {
-            self.status = Some(status);
+            self.set_status(status);
         }
 
         if other.body().is_some() {
             self.body = other.body;
         }
 
+        if other.reason.is_some() {
+            self.reason = other.reason;
+        }
+
+        if other.upgrade.is_some() {
+            self.upgrade = other.upgrade;
+        }
+
         for (name, values) in other.headers.into_iter_raw() {
             self.headers.replace_all(name.into_cow(), values);
         }
@@ -918,10 +1162,18 @@ impl<'r> Response<'r> {
             self.status = other.status;
         }
 
+        if self.reason.is_none() && self.status == other.status {
+            self.reason = other.reason;
+        }
+
         if self.body.is_none() {
             self.body = other.body;
         }
 
+        if self.upgrade.is_none() {
+            self.upgrade = other.upgrade;
+        }
+
         for (name, mut values) in other.headers.into_iter_raw() {
             self.headers.add_all(name.into_cow(), &mut values);
         }
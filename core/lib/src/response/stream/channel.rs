@@ -0,0 +1,152 @@
+use std::{fmt, io};
+use std::error::Error;
+
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::sync::mpsc;
+use tokio_util::io::StreamReader;
+
+use crate::request::Request;
+use crate::response::{self, Response, Responder};
+use crate::http::ContentType;
+use crate::http::hyper::body::Bytes;
+
+/// A stream of bytes received from an [`mpsc::Receiver`].
+///
+/// A `ChannelStream` streams out chunks of bytes as they are sent to the
+/// receiver's channel, typically by a spawned task, and completes when every
+/// sender has been dropped. Chunks can be of any type that converts into
+/// [`Bytes`]: `Bytes`, `Vec<u8>`, `String`, `&'static str`, and more. To end
+/// the stream with an error, construct it with [`ChannelStream::fallible()`]
+/// from a receiver of `Result`s.
+///
+/// [`mpsc::Receiver`]: tokio::sync::mpsc::Receiver
+///
+/// # Responder
+///
+/// `ChannelStream` is a (potentially infinite) responder. The response
+/// `Content-Type` is set to [`Binary`](ContentType::Binary) unless another is
+/// set with [`ChannelStream::content_type()`]. The body is
+/// [unsized](crate::response::Body#unsized), and chunks are sent as soon as
+/// they are received. If a chunk is an error, the connection is closed
+/// without completing the response.
+///
+/// # Example
+///
+/// Stream lines produced by a background task:
+///
+/// ```rust
+/// # use rocket::*;
+/// use rocket::response::stream::ChannelStream;
+/// use rocket::http::ContentType;
+/// use rocket::tokio::{self, sync::mpsc};
+///
+/// #[get("/lines")]
+/// fn lines() -> ChannelStream {
+///     let (tx, rx) = mpsc::channel(8);
+///     tokio::spawn(async move {
+///         for i in 0..3 {
+///             if tx.send(format!("line {}\n", i)).await.is_err() {
+///                 break;
+///             }
+///         }
+///     });
+///
+///     ChannelStream::new(rx).content_type(ContentType::Plain)
+/// }
+/// ```
+pub struct ChannelStream {
+    stream: BoxStream<'static, io::Result<Bytes>>,
+    content_type: ContentType,
+}
+
+impl ChannelStream {
+    /// Creates a `ChannelStream` that streams the chunks received by
+    /// `receiver` with a `Content-Type` of `application/octet-stream`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::stream::ChannelStream;
+    /// use rocket::tokio::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel::<Vec<u8>>(8);
+    /// let stream = ChannelStream::new(rx);
+    /// ```
+    pub fn new<T>(mut receiver: mpsc::Receiver<T>) -> Self
+        where T: Into<Bytes> + Send + 'static
+    {
+        let stream = stream::poll_fn(move |cx| receiver.poll_recv(cx))
+            .map(|chunk| Ok(chunk.into()));
+
+        ChannelStream { stream: stream.boxed(), content_type: ContentType::Binary }
+    }
+
+    /// Creates a `ChannelStream` that streams the chunks received by
+    /// `receiver` until an `Err` is received, with a `Content-Type` of
+    /// `application/octet-stream`. An `Err` ends the stream with an error,
+    /// which closes the connection without completing the response.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    ///
+    /// use rocket::response::stream::ChannelStream;
+    /// use rocket::tokio::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel::<io::Result<String>>(8);
+    /// let stream = ChannelStream::fallible(rx);
+    /// ```
+    pub fn fallible<T, E>(mut receiver: mpsc::Receiver<Result<T, E>>) -> Self
+        where T: Into<Bytes> + Send + 'static,
+              E: Into<Box<dyn Error + Send + Sync>> + Send + 'static
+    {
+        let stream = stream::poll_fn(move |cx| receiver.poll_recv(cx))
+            .map(|chunk| match chunk {
+                Ok(bytes) => Ok(bytes.into()),
+                Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+            });
+
+        ChannelStream { stream: stream.boxed(), content_type: ContentType::Binary }
+    }
+
+    /// Sets the `Content-Type` of the response to `content_type`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::stream::ChannelStream;
+    /// use rocket::http::ContentType;
+    /// use rocket::tokio::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel::<String>(8);
+    /// let stream = ChannelStream::new(rx).content_type(ContentType::Plain);
+    /// ```
+    pub fn content_type(mut self, content_type: ContentType) -> Self {
+        self.content_type = content_type;
+        self
+    }
+}
+
+impl<T: Into<Bytes> + Send + 'static> From<mpsc::Receiver<T>> for ChannelStream {
+    fn from(receiver: mpsc::Receiver<T>) -> Self {
+        ChannelStream::new(receiver)
+    }
+}
+
+impl fmt::Debug for ChannelStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChannelStream")
+            .field("content_type", &self.content_type)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ChannelStream {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .header(self.content_type)
+            .streamed_body(StreamReader::new(self.stream))
+            .ok()
+    }
+}
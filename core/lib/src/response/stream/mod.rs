@@ -151,6 +151,12 @@
 //! }
 //! ```
 //!
+//! # Channels
+//!
+//! When chunks are produced elsewhere, say by a spawned task, a
+//! [`ChannelStream`] streams them from the receiving half of a
+//! [`tokio::sync::mpsc`] channel, completing when the sender is dropped.
+//!
 //! # Graceful Shutdown
 //!
 //! Infinite responders, like the one defined in `hello` above, will prolong
@@ -194,6 +200,7 @@ mod text;
 mod one;
 mod sse;
 mod raw_sse;
+mod channel;
 
 pub(crate) use self::raw_sse::*;
 
@@ -202,6 +209,7 @@ pub use self::text::TextStream;
 pub use self::bytes::ByteStream;
 pub use self::reader::ReaderStream;
 pub use self::sse::{Event, EventStream};
+pub use self::channel::ChannelStream;
 
 crate::export! {
     /// Retrofitted support for [`Stream`]s with `yield`, `for await` syntax.
@@ -363,6 +363,13 @@ impl Event {
 /// defaults to 30 seconds but can be adjusted with
 /// [`EventStream::heartbeat()`].
 ///
+/// Heartbeats are sent while the internal stream is pending, so a stream that
+/// stalls doesn't cause the connection to go quiet. The interval is measured
+/// from the previous heartbeat's transmission: if writing to a slow client
+/// stalls for longer than the interval, heartbeats resume at the regular
+/// interval once the write completes instead of one being sent for every
+/// missed interval.
+///
 /// # Examples
 ///
 /// Use [`EventStream!`] to yield an infinite series of "ping" SSE messages to
@@ -519,12 +526,13 @@ impl<S: Stream<Item = Event>> EventStream<S> {
     }
 
     fn heartbeat_stream(&self) -> Option<impl Stream<Item = RawLinedEvent>> {
-        use tokio::time::interval;
-        use tokio_stream::wrappers::IntervalStream;
-
-        self.heartbeat
-            .map(|beat| IntervalStream::new(interval(beat)))
-            .map(|stream| stream.map(|_| RawLinedEvent::raw(":")))
+        // Each beat is timed from when the previous one was taken. Beats that
+        // come due while a write to a slow client is pending are thus not sent
+        // as a burst once the write completes.
+        self.heartbeat.map(|beat| stream::unfold((), move |_| async move {
+            tokio::time::sleep(beat).await;
+            Some((RawLinedEvent::raw(":"), ()))
+        }))
     }
 
     fn into_stream(self) -> impl Stream<Item = RawLinedEvent> {
@@ -757,6 +765,31 @@ mod sse_tests {
             "data:a\ndata:b\n\ndata:b\n\ndata:c\ndata:\ndata:d\n\ndata:e\n\n");
     }
 
+    #[test]
+    fn test_heartbeat_after_slow_write() {
+        use futures::FutureExt;
+        use futures::stream::{pending, StreamExt};
+
+        // Stall the producer and take a first beat, then simulate a write to
+        // a slow client taking 5 intervals. The missed beats shouldn't burst.
+        let raw = pending::<Event>();
+        let mut stream = Box::pin(EventStream::from(raw)
+            .heartbeat(Duration::from_millis(100))
+            .into_stream());
+
+        crate::async_test(async move {
+            assert!(stream.next().await.is_some());
+            time::sleep(Duration::from_millis(500)).await;
+
+            let mut ready = 0;
+            while let Some(Some(_)) = stream.next().now_or_never() {
+                ready += 1;
+            }
+
+            assert!(ready <= 1, "{} beats ready after slow write", ready);
+        })
+    }
+
     #[test]
     fn test_heartbeat() {
         use futures::future::ready;
@@ -0,0 +1,119 @@
+//! Raw, protocol-agnostic connection upgrades.
+//!
+//! An [`Upgrade`] responds with `101 Switching Protocols` and, once the
+//! response has been written, hands the raw connection to a user-supplied
+//! handler. No framing of any kind is applied to the connection: the handler
+//! reads and writes bytes directly via the [`AsyncRead`] and [`AsyncWrite`]
+//! implementations of [`Upgraded`].
+//!
+//! [`AsyncRead`]: tokio::io::AsyncRead
+//! [`AsyncWrite`]: tokio::io::AsyncWrite
+//!
+//! # Example
+//!
+//! An echo protocol that writes back every byte it reads:
+//!
+//! ```rust
+//! # use rocket::get;
+//! use rocket::tokio::io;
+//! use rocket::response::upgrade::Upgrade;
+//!
+//! #[get("/echo")]
+//! fn echo() -> Upgrade {
+//!     Upgrade::new("echo", |stream| async move {
+//!         let (mut reader, mut writer) = io::split(stream);
+//!         io::copy(&mut reader, &mut writer).await?;
+//!         Ok(())
+//!     })
+//! }
+//! ```
+
+use std::io;
+use std::borrow::Cow;
+
+use futures::future::{Future, BoxFuture, FutureExt};
+
+use crate::request::Request;
+use crate::response::{self, Response, Responder};
+use crate::http::{uncased, Status};
+
+/// A raw, upgraded connection. Implements `AsyncRead` and `AsyncWrite`.
+pub use crate::http::hyper::upgrade::Upgraded;
+
+/// A boxed upgrade handler, as stored in a [`Response`].
+pub(crate) type UpgradeHandler =
+    Box<dyn FnOnce(Upgraded) -> BoxFuture<'static, io::Result<()>> + Send + 'static>;
+
+/// A responder that upgrades the connection to a raw stream.
+///
+/// If the request's `Upgrade` header lists `protocol`, ASCII
+/// case-insensitively, responds with `101 Switching Protocols`, a `Connection:
+/// Upgrade` header, and an `Upgrade` header naming `protocol`. Once the
+/// response is written, the connection is passed to the handler. Otherwise,
+/// responds with `426 Upgrade Required` and an `Upgrade` header naming
+/// `protocol`, and the handler is never called.
+///
+/// Additional headers can be added to the `101` response by composing with
+/// other responders, for instance with a [custom responder].
+///
+/// _**Note:** The local testing client does not support upgrades. Handlers are
+/// never called for responses dispatched via [`local`](crate::local)._
+///
+/// See the [module level docs](self) for an example.
+///
+/// [custom responder]: crate::response::Responder#deriving
+pub struct Upgrade {
+    protocol: Cow<'static, str>,
+    handler: UpgradeHandler,
+}
+
+impl Upgrade {
+    /// Creates an upgrade to `protocol` whose connection will be handled by
+    /// `handler`. An error returned from `handler` is logged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::tokio::io::AsyncWriteExt;
+    /// use rocket::response::upgrade::Upgrade;
+    ///
+    /// let upgrade = Upgrade::new("hello", |mut stream| async move {
+    ///     stream.write_all(b"hello, world!").await
+    /// });
+    /// ```
+    pub fn new<P, F, Fut>(protocol: P, handler: F) -> Upgrade
+        where P: Into<Cow<'static, str>>,
+              F: FnOnce(Upgraded) -> Fut + Send + 'static,
+              Fut: Future<Output = io::Result<()>> + Send + 'static,
+    {
+        Upgrade {
+            protocol: protocol.into(),
+            handler: Box::new(move |stream| handler(stream).boxed()),
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for Upgrade {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let requested = req.headers().get("Upgrade")
+            .flat_map(|value| value.split(','))
+            .any(|protocol| uncased::eq(protocol.trim(), &self.protocol));
+
+        if !requested {
+            warn_!("Request did not ask to upgrade to {}.", self.protocol);
+            return Response::build()
+                .status(Status::UpgradeRequired)
+                .raw_header("Upgrade", self.protocol)
+                .ok();
+        }
+
+        let mut response = Response::build()
+            .status(Status::SwitchingProtocols)
+            .raw_header("Connection", "Upgrade")
+            .raw_header("Upgrade", self.protocol)
+            .finalize();
+
+        response.set_upgrade(self.handler);
+        Ok(response)
+    }
+}
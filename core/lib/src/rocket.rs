@@ -8,12 +8,14 @@ use figment::{Figment, Provider};
 
 use crate::{Catcher, Config, Route, Shutdown, sentinel, shield::Shield};
 use crate::router::Router;
+use crate::data::BodyBudget;
 use crate::trip_wire::TripWire;
 use crate::fairing::{Fairing, Fairings};
 use crate::phase::{Phase, Build, Building, Ignite, Igniting, Orbit, Orbiting};
 use crate::phase::{Stateful, StateRef, State};
 use crate::http::uri::{self, Origin};
 use crate::http::ext::IntoOwned;
+use crate::listener::Listener;
 use crate::error::{Error, ErrorKind};
 use crate::log::PaintExt;
 
@@ -515,7 +517,6 @@ impl Rocket<Build> {
         self.fairings.audit().map_err(|f| ErrorKind::FailedFairings(f.to_vec()))?;
 
         // Extract the configuration; initialize the logger.
-        #[allow(unused_mut)]
         let mut config = Config::try_from(&self.figment).map_err(ErrorKind::Config)?;
         crate::log::init(&config);
 
@@ -532,6 +533,14 @@ impl Rocket<Build> {
             }
         };
 
+        // The HTTP/1 server rejects requests with more headers regardless.
+        if config.max_headers > Config::MAX_HEADERS_CAP {
+            warn!("`{}` of {} exceeds the HTTP/1 server's maximum of {}",
+                Config::MAX_HEADERS, config.max_headers, Config::MAX_HEADERS_CAP);
+            launch_info_!("using a `{}` of {}", Config::MAX_HEADERS, Config::MAX_HEADERS_CAP);
+            config.max_headers = Config::MAX_HEADERS_CAP;
+        }
+
         // Initialize the router; check for collisions.
         let mut router = Router::new();
         self.routes.clone().into_iter().for_each(|r| router.add_route(r));
@@ -636,6 +645,7 @@ impl Rocket<Ignite> {
     }
 
     fn into_orbit(self) -> Rocket<Orbit> {
+        let body_budget = BodyBudget::new(self.0.config.max_total_body_bytes);
         Rocket(Orbiting {
             router: self.0.router,
             fairings: self.0.fairings,
@@ -643,6 +653,8 @@ impl Rocket<Ignite> {
             config: self.0.config,
             state: self.0.state,
             shutdown: self.0.shutdown,
+            in_flight: Default::default(),
+            body_budget,
         })
     }
 
@@ -671,6 +683,29 @@ impl Rocket<Ignite> {
             .await
             .map(|rocket| rocket.into_ignite())
     }
+
+    async fn _launch_on<L>(self, listener: L) -> Result<Rocket<Ignite>, Error>
+        where L: Listener + Send, <L as Listener>::Connection: Send + Unpin + 'static
+    {
+        let mut rocket = self.into_orbit();
+        if let Some(addr) = listener.local_addr() {
+            rocket.config.address = addr.ip();
+            rocket.config.port = addr.port();
+        }
+
+        rocket.fairings.handle_liftoff(&rocket).await;
+        match listener.local_addr() {
+            Some(addr) => launch_info!("{}{} {}",
+                Paint::emoji("🚀 "),
+                Paint::default("Rocket has launched on").bold(),
+                Paint::default(addr).bold().underline()),
+            None => launch_info!("{}{}",
+                Paint::emoji("🚀 "),
+                Paint::default("Rocket has launched on a custom listener").bold()),
+        }
+
+        rocket.http_server(listener).await.map(|rocket| rocket.into_ignite())
+    }
 }
 
 impl Rocket<Orbit> {
@@ -901,6 +936,40 @@ impl<P: Phase> Rocket<P> {
             State::Orbit(s) => Ok(Rocket::from(s).into_ignite())
         }
     }
+
+    /// Like [`Rocket::launch()`], but serves connections accepted from
+    /// `listener` instead of binding to the configured address and port.
+    ///
+    /// This allows serving Rocket over a custom transport, such as an
+    /// in-memory pipe or a proxied stream. The configured `address`, `port`,
+    /// and `tls` settings are ignored except that, if `listener` reports a
+    /// [local address](Listener::local_addr()), the `address` and `port` in
+    /// [`Rocket::config()`] are set to it before liftoff fairings run.
+    ///
+    /// See the [`listener`](crate::listener) module for a complete example
+    /// that serves requests over an in-memory duplex stream.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rocket::tokio::net::TcpListener;
+    ///
+    /// #[rocket::main]
+    /// async fn main() -> Result<(), rocket::Error> {
+    ///     let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    ///     let _rocket = rocket::build().launch_on(listener).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn launch_on<L>(self, listener: L) -> Result<Rocket<Ignite>, Error>
+        where L: Listener + Send, <L as Listener>::Connection: Send + Unpin + 'static
+    {
+        match self.0.into_state() {
+            State::Build(s) => Rocket::from(s).ignite().await?._launch_on(listener).await,
+            State::Ignite(s) => Rocket::from(s)._launch_on(listener).await,
+            State::Orbit(s) => Ok(Rocket::from(s).into_ignite())
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -188,8 +188,21 @@ pub struct Route {
     pub rank: isize,
     /// The media type this route matches against, if any.
     pub format: Option<MediaType>,
+    /// The name of the [limit](crate::data::Limits) that applies to this
+    /// route's data, if any. When set, the limit is exposed to the data guard
+    /// via [`Data::limit()`](crate::Data::limit()).
+    pub limit: Option<Cow<'static, str>>,
+    /// Whether this route, if it is a `GET` route, handles `HEAD` requests
+    /// that no `HEAD` route handles. When `false`, such requests aren't
+    /// autohandled by this route. Defaults to `true`.
+    pub auto_head: bool,
     /// The discovered sentinels.
     pub(crate) sentinels: Vec<Sentry>,
+    /// Whether this route's method is reported as allowed for the paths it
+    /// matches, as in the `Allow` header of a `405` response. `false` only for
+    /// internal routes that handle a narrow class of requests, like CORS
+    /// preflights, for any path.
+    pub(crate) advertised: bool,
 }
 
 impl Route {
@@ -252,7 +265,10 @@ impl Route {
         Route {
             name: None,
             format: None,
+            limit: None,
+            auto_head: true,
             sentinels: Vec::new(),
+            advertised: true,
             handler: Box::new(handler),
             rank, uri, method,
         }
@@ -325,6 +341,8 @@ impl fmt::Debug for Route {
             .field("uri", &self.uri)
             .field("rank", &self.rank)
             .field("format", &self.format)
+            .field("limit", &self.limit)
+            .field("auto_head", &self.auto_head)
             .finish()
     }
 }
@@ -344,6 +362,10 @@ pub struct StaticInfo {
     pub handler: for<'r> fn(&'r crate::Request<'_>, crate::Data<'r>) -> BoxFuture<'r>,
     /// The route's rank, if any.
     pub rank: Option<isize>,
+    /// The route's data limit name, if any.
+    pub limit: Option<&'static str>,
+    /// Whether the route autohandles `HEAD` requests.
+    pub auto_head: bool,
     /// Route-derived sentinels, if any.
     /// This isn't `&'static [SentryInfo]` because `type_name()` isn't `const`.
     pub sentinels: Vec<Sentry>,
@@ -361,7 +383,10 @@ impl From<StaticInfo> for Route {
             handler: Box::new(info.handler),
             rank: info.rank.unwrap_or_else(|| uri.default_rank()),
             format: info.format,
+            limit: info.limit.map(|name| name.into()),
+            auto_head: info.auto_head,
             sentinels: info.sentinels.into_iter().collect(),
+            advertised: true,
             uri,
         }
     }
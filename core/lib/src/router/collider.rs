@@ -3,6 +3,7 @@ use crate::route::{Route, Color};
 
 use crate::http::{MediaType, Status};
 use crate::request::Request;
+use crate::config::TrailingSlash;
 
 pub trait Collide<T = Self> {
     fn collides_with(&self, other: &T) -> bool;
@@ -96,12 +97,44 @@ impl Route {
     ///   * All static components in the route's query string are also in the
     ///     request query string, though in any position. If there is no query
     ///     in the route, requests with/without queries match.
+    ///
+    /// Whether a trailing slash in the request's path is significant is
+    /// determined by [`Config::trailing_slash`](crate::Config::trailing_slash).
     pub(crate) fn matches(&self, req: &Request<'_>) -> bool {
         self.method == req.method()
+            && slashes_match(self, req)
             && paths_match(self, req)
             && queries_match(self, req)
             && formats_match(self, req)
     }
+
+    /// Determines if this route matches the given request's path and query,
+    /// irrespective of method and format.
+    pub(crate) fn matches_path(&self, req: &Request<'_>) -> bool {
+        slashes_match(self, req) && paths_match(self, req) && queries_match(self, req)
+    }
+
+    /// Determines if this route would match the given request if the request's
+    /// path had no trailing slash.
+    pub(crate) fn matches_without_slash(&self, req: &Request<'_>) -> bool {
+        self.method == req.method()
+            && paths_match(self, req)
+            && queries_match(self, req)
+            && formats_match(self, req)
+    }
+}
+
+/// Returns `true` if `req`'s path, other than `/`, ends in a slash.
+pub(crate) fn has_trailing_slash(req: &Request<'_>) -> bool {
+    let path = req.uri().path();
+    path.len() > 1 && path.ends_with('/')
+}
+
+fn slashes_match(route: &Route, req: &Request<'_>) -> bool {
+    // Empty segments aren't segments, so `paths_match` ignores the slash.
+    req.rocket().config().trailing_slash == TrailingSlash::Ignore
+        || route.uri.metadata.trailing_path
+        || !has_trailing_slash(req)
 }
 
 fn paths_match(route: &Route, req: &Request<'_>) -> bool {
@@ -55,6 +55,41 @@ impl Router {
             .flat_map(move |routes| routes.iter().filter(move |r| r.matches(req)))
     }
 
+    /// Returns `true` if a route would match `req` if its path had no trailing
+    /// slash.
+    pub fn matches_without_slash(&self, req: &Request<'_>) -> bool {
+        self.routes.get(&req.method())
+            .map_or(false, |routes| routes.iter().any(|r| r.matches_without_slash(req)))
+    }
+
+    /// Returns the methods of routes that match `req`'s path and query,
+    /// irrespective of method, in a stable order. `HEAD` is included when a
+    /// matching `GET` route allows `HEAD` autohandling. Routes that aren't
+    /// advertised are ignored.
+    pub fn allowed_methods(&self, req: &Request<'_>) -> Vec<Method> {
+        let mut methods = vec![];
+        for route in self.routes().filter(|r| r.advertised && r.matches_path(req)) {
+            methods.push(route.method);
+            if route.method == Method::Get && route.auto_head {
+                methods.push(Method::Head);
+            }
+        }
+
+        methods.sort_by_key(|&method| method as u8);
+        methods.dedup();
+        methods
+    }
+
+    /// Returns the advertised routes that match `req`'s path and query but not
+    /// its method.
+    pub fn method_mismatches<'r, 'a: 'r>(
+        &'a self,
+        req: &'r Request<'r>
+    ) -> impl Iterator<Item = &'a Route> + 'r {
+        self.routes()
+            .filter(move |r| r.advertised && r.method != req.method() && r.matches_path(req))
+    }
+
     // For many catchers, using aho-corasick or similar should be much faster.
     pub fn catch<'r>(&self, status: Status, req: &'r Request<'r>) -> Option<&Catcher> {
         // Note that catchers are presorted by descending base length.
@@ -28,7 +28,7 @@ use std::{io, fmt, error};
 use std::ops::{Deref, DerefMut};
 
 use crate::request::{Request, local_cache};
-use crate::data::{Limits, Data, FromData, Outcome};
+use crate::data::{Limits, Data, FromData, Outcome, ByteUnit};
 use crate::response::{self, Responder, content};
 use crate::form::prelude as form;
 use crate::http::uri::fmt::{UriDisplay, FromUriParam, Query, Formatter as UriFormatter};
@@ -178,8 +178,14 @@ impl<'r, T: Deserialize<'r>> Json<T> {
         serde_json::from_str(s).map(Json).map_err(|e| Error::Parse(s, e))
     }
 
+    fn limit(req: &Request<'_>, data: &Data<'_>) -> ByteUnit {
+        data.limit()
+            .or_else(|| req.limits().get("json"))
+            .unwrap_or(Limits::JSON)
+    }
+
     async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Result<Self, Error<'r>> {
-        let limit = req.limits().get("json").unwrap_or(Limits::JSON);
+        let limit = Self::limit(req, &data);
         let string = match data.open(limit).into_string().await {
             Ok(s) if s.is_complete() => s.into_inner(),
             Ok(_) => {
@@ -198,6 +204,11 @@ impl<'r, T: Deserialize<'r>> FromData<'r> for Json<T> {
     type Error = Error<'r>;
 
     async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        if let Err(status) = data.precheck_strict(Self::limit(req, &data)) {
+            let e = io::Error::new(io::ErrorKind::InvalidInput, "data limit exceeded");
+            return Outcome::Failure((status, Error::Io(e)));
+        }
+
         match Self::from_data(req, data).await {
             Ok(value) => Outcome::Success(value),
             Err(Error::Io(e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
@@ -28,7 +28,7 @@ use std::io;
 use std::ops::{Deref, DerefMut};
 
 use crate::request::{Request, local_cache};
-use crate::data::{Limits, Data, FromData, Outcome};
+use crate::data::{Limits, Data, FromData, Outcome, ByteUnit};
 use crate::response::{self, Responder, content};
 use crate::http::Status;
 use crate::form::prelude as form;
@@ -147,8 +147,14 @@ impl<'r, T: Deserialize<'r>> MsgPack<T> {
         rmp_serde::from_slice(buf).map(MsgPack)
     }
 
+    fn limit(req: &Request<'_>, data: &Data<'_>) -> ByteUnit {
+        data.limit()
+            .or_else(|| req.limits().get("msgpack"))
+            .unwrap_or(Limits::MESSAGE_PACK)
+    }
+
     async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Result<Self, Error> {
-        let limit = req.limits().get("msgpack").unwrap_or(Limits::MESSAGE_PACK);
+        let limit = Self::limit(req, &data);
         let bytes = match data.open(limit).into_bytes().await {
             Ok(buf) if buf.is_complete() => buf.into_inner(),
             Ok(_) => {
@@ -167,6 +173,11 @@ impl<'r, T: Deserialize<'r>> FromData<'r> for MsgPack<T> {
     type Error = Error;
 
     async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        if let Err(status) = data.precheck_strict(Self::limit(req, &data)) {
+            let e = io::Error::new(io::ErrorKind::InvalidInput, "data limit exceeded");
+            return Outcome::Failure((status, Error::InvalidDataRead(e)));
+        }
+
         match Self::from_data(req, data).await {
             Ok(value) => Outcome::Success(value),
             Err(Error::InvalidDataRead(e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
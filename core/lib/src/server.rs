@@ -1,5 +1,7 @@
 use std::io;
+use std::ops::Deref;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
 use yansi::Paint;
@@ -13,9 +15,10 @@ use crate::form::Form;
 use crate::outcome::Outcome;
 use crate::error::{Error, ErrorKind};
 use crate::ext::{AsyncReadExt, CancellableListener, CancellableIo};
-use crate::request::ConnectionMeta;
+use crate::request::{ConnectionMeta, ForwardReason};
+use crate::config::TrailingSlash;
 
-use crate::http::{hyper, Method, Status, Header};
+use crate::http::{hyper, uncased, Method, Status, Header};
 use crate::http::private::{TcpListener, Listener, Connection, Incoming};
 
 // A token returned to force the execution of one method before another.
@@ -64,7 +67,8 @@ async fn handle<Fut, T, F>(name: Option<&str>, run: F) -> Option<T>
 async fn hyper_service_fn(
     rocket: Arc<Rocket<Orbit>>,
     conn: ConnectionMeta,
-    hyp_req: hyper::Request<hyper::Body>,
+    conn_in_flight: Option<Arc<AtomicUsize>>,
+    mut hyp_req: hyper::Request<hyper::Body>,
 ) -> Result<hyper::Response<hyper::Body>, io::Error> {
     // This future must return a hyper::Response, but the response body might
     // borrow from the request. Instead, write the body in another future that
@@ -72,23 +76,40 @@ async fn hyper_service_fn(
     let (tx, rx) = oneshot::channel();
 
     tokio::spawn(async move {
+        // Keep the connection from being considered idle until we're done.
+        let _in_flight = conn_in_flight.map(InFlight::new);
+
         // Convert a Hyper request into a Rocket request.
+        let upgrade = hyper::upgrade::on(&mut hyp_req);
         let (h_parts, mut h_body) = hyp_req.into_parts();
         match Request::from_hyp(&rocket, &h_parts, Some(conn)) {
+            Ok(req) if rocket.check_header_limits(&req).is_err() => {
+                let response = rocket.handle_error(Status::RequestHeaderFieldsTooLarge, &req).await;
+                rocket.send_response(response, upgrade, tx).await;
+            }
             Ok(mut req) => {
                 // Convert into Rocket `Data`, dispatch request, write response.
                 let mut data = Data::from(&mut h_body);
+                data.flag_aborted(req.state.body_aborted.clone());
                 let token = rocket.preprocess_request(&mut req, &mut data).await;
-                let response = rocket.dispatch(token, &mut req, data).await;
-                rocket.send_response(response, tx).await;
+                let response = rocket.dispatch(token, &req, data).await;
+
+                // If the client left mid-body, there's no one to respond to.
+                // Dropping `tx` closes the connection without a response.
+                if req.body_aborted() {
+                    warn_!("Client disconnected while sending the body. Not responding.");
+                    return;
+                }
+
+                rocket.send_response(response, upgrade, tx).await;
             },
             Err(e) => {
                 warn!("Bad incoming HTTP request.");
                 e.errors.iter().for_each(|e| warn_!("Error: {}.", e));
                 warn_!("Dispatching salvaged request to catcher: {}.", e.request);
 
-                let response = rocket.handle_error(Status::BadRequest, &e.request).await;
-                rocket.send_response(response, tx).await;
+                let response = rocket.handle_error(e.status(), &e.request).await;
+                rocket.send_response(response, upgrade, tx).await;
             }
         }
     });
@@ -97,12 +118,32 @@ async fn hyper_service_fn(
     rx.await.map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))
 }
 
+/// A request counted in a counter of in-flight requests until dropped.
+struct InFlight<C: Deref<Target = AtomicUsize>> {
+    counter: C,
+    count: usize,
+}
+
+impl<C: Deref<Target = AtomicUsize>> InFlight<C> {
+    fn new(counter: C) -> Self {
+        let count = counter.fetch_add(1, Ordering::AcqRel) + 1;
+        InFlight { counter, count }
+    }
+}
+
+impl<C: Deref<Target = AtomicUsize>> Drop for InFlight<C> {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
 impl Rocket<Orbit> {
     /// Wrapper around `_send_response` to log a success or failure.
     #[inline]
     async fn send_response(
         &self,
         response: Response<'_>,
+        upgrade: hyper::upgrade::OnUpgrade,
         tx: oneshot::Sender<hyper::Response<hyper::Body>>,
     ) {
         let remote_hungup = |e: &io::Error| match e.kind() {
@@ -112,7 +153,7 @@ impl Rocket<Orbit> {
             _ => false,
         };
 
-        match self._send_response(response, tx).await {
+        match self._send_response(response, upgrade, tx).await {
             Ok(()) => info_!("{}", Paint::green("Response succeeded.")),
             Err(e) if remote_hungup(&e) => warn_!("Remote left: {}.", e),
             Err(e) => warn_!("Failed to write response: {}.", e),
@@ -120,15 +161,54 @@ impl Rocket<Orbit> {
     }
 
     /// Attempts to create a hyper response from `response` and send it to `tx`.
+    /// If `response` is a `101` with an upgrade handler, the handler is spawned
+    /// to run on the connection once `upgrade` resolves.
     #[inline]
     async fn _send_response(
         &self,
         mut response: Response<'_>,
+        upgrade: hyper::upgrade::OnUpgrade,
         tx: oneshot::Sender<hyper::Response<hyper::Body>>,
     ) -> io::Result<()> {
+        let handler = response.take_upgrade()
+            .filter(|_| response.status() == Status::SwitchingProtocols);
+
+        if let Some(handler) = handler {
+            let hyp_res = hyper::Response::builder().status(response.status().code);
+            let hyp_res = response.headers().iter()
+                .fold(hyp_res, |res, h| res.header(h.name.as_str(), h.value.as_bytes()))
+                .body(hyper::Body::empty())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            tx.send(hyp_res).map_err(|_| {
+                let msg = "client disconnect before response started";
+                io::Error::new(io::ErrorKind::BrokenPipe, msg)
+            })?;
+
+            tokio::spawn(async move {
+                let result = match upgrade.await {
+                    Ok(stream) => handler(stream).await,
+                    Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+                };
+
+                if let Err(e) = result {
+                    warn!("Upgraded connection failed: {}.", e);
+                }
+            });
+
+            return Ok(());
+        }
+
         let mut hyp_res = hyper::Response::builder();
 
         hyp_res = hyp_res.status(response.status().code);
+        if let Some(reason) = response.reason_phrase() {
+            match hyper::ext::ReasonPhrase::try_from(reason.as_bytes()) {
+                Ok(reason) => hyp_res = hyp_res.extension(reason),
+                Err(_) => warn_!("Ignoring invalid reason phrase: {:?}.", reason),
+            }
+        }
+
         for header in response.headers().iter() {
             let name = header.name.as_str();
             let value = header.value.as_bytes();
@@ -136,8 +216,10 @@ impl Rocket<Orbit> {
         }
 
         let body = response.body_mut();
-        if let Some(n) = body.size().await {
-            hyp_res = hyp_res.header(hyper::header::CONTENT_LENGTH, n);
+        if !body.is_force_chunked() {
+            if let Some(n) = body.size().await {
+                hyp_res = hyp_res.header(hyper::header::CONTENT_LENGTH, n);
+            }
         }
 
         let (mut sender, hyp_body) = hyper::Body::channel();
@@ -159,9 +241,33 @@ impl Rocket<Orbit> {
         Ok(())
     }
 
+    /// Checks that `req` has no more headers, and no larger headers, than
+    /// allowed by the `max_headers` and `max_header_size` config parameters.
+    pub(crate) fn check_header_limits(&self, req: &Request<'_>) -> Result<(), Status> {
+        let headers = req.headers();
+        if headers.len() > self.config.max_headers {
+            warn_!("Request has {} headers, more than the maximum of {}.",
+                headers.len(), self.config.max_headers);
+
+            return Err(Status::RequestHeaderFieldsTooLarge);
+        }
+
+        let size: usize = headers.iter().map(|h| h.name().len() + h.value().len()).sum();
+        if size as u64 > self.config.max_header_size.as_u64() {
+            warn_!("Request headers are {} bytes, more than the maximum of {}.",
+                size, self.config.max_header_size);
+
+            return Err(Status::RequestHeaderFieldsTooLarge);
+        }
+
+        Ok(())
+    }
+
     /// Preprocess the request for Rocket things. Currently, this means:
     ///
     ///   * Rewriting the method in the request if _method form field exists.
+    ///   * Rewriting the method in the request if the `X-HTTP-Method-Override`
+    ///     header exists and `header_method_override` is enabled.
     ///   * Run the request fairings.
     ///
     /// Keep this in-sync with derive_form when preprocessing form fields.
@@ -170,17 +276,42 @@ impl Rocket<Orbit> {
         req: &mut Request<'_>,
         data: &mut Data<'_>
     ) -> RequestToken {
+        // Record the declared body length and whether the client is waiting on
+        // a `100 Continue` before sending the body. This lets data guards reject
+        // a request without the body ever being transferred.
+        data.declared_len = req.headers().get_one("Content-Length")
+            .and_then(|len| len.trim().parse().ok());
+
+        data.expects_continue = req.headers().get_one("Expect")
+            .map_or(false, |expect| uncased::eq(expect.trim(), "100-continue"));
+
         // Check if this is a form and if the form contains the special _method
-        // field which we use to reinterpret the request's method.
+        // field which we use to reinterpret the request's method. We only peek
+        // when necessary as peeking sends the interim `100 Continue`, if any.
         let (min_len, max_len) = ("_method=get".len(), "_method=delete".len());
-        let peek_buffer = data.peek(max_len).await;
         let is_form = req.content_type().map_or(false, |ct| ct.is_form());
 
-        if is_form && req.method() == Method::Post && peek_buffer.len() >= min_len {
-            let method = std::str::from_utf8(peek_buffer).ok()
-                .and_then(|raw_form| Form::values(raw_form).next())
-                .filter(|field| field.name == "_method")
-                .and_then(|field| field.value.parse().ok());
+        let mut form_method = None;
+        if is_form && req.method() == Method::Post {
+            let peek_buffer = data.peek(max_len).await;
+            if peek_buffer.len() >= min_len {
+                form_method = std::str::from_utf8(peek_buffer).ok()
+                    .and_then(|raw_form| Form::values(raw_form).next())
+                    .filter(|field| field.name == "_method")
+                    .and_then(|field| field.value.parse().ok());
+            }
+
+            if let Some(method) = form_method {
+                req._set_method(method);
+            }
+        }
+
+        // If enabled, check for the `X-HTTP-Method-Override` header. A `_method`
+        // form field, handled above, takes precedence over the header.
+        let header_override = self.config.header_method_override;
+        if header_override && form_method.is_none() && req.method() == Method::Post {
+            let method = req.headers().get_one("X-HTTP-Method-Override")
+                .and_then(|value| value.trim().parse().ok());
 
             if let Some(method) = method {
                 req._set_method(method);
@@ -201,12 +332,23 @@ impl Rocket<Orbit> {
         data: Data<'r>
     ) -> Response<'r> {
         info!("{}:", request);
+        request.set_start_time();
 
         // Remember if the request is `HEAD` for later body stripping.
         let was_head_request = request.method() == Method::Head;
 
-        // Route the request and run the user's handlers.
-        let mut response = self.route_and_process(request, data).await;
+        // Route the request and run the user's handlers unless we're shedding
+        // load, in which case the client is asked not to reuse the connection.
+        let mut response = match self.admit_request() {
+            Some(_in_flight) => self.route_and_process(request, data).await,
+            None => {
+                warn_!("Too many concurrent requests. Shedding load.");
+                let mut response = self.handle_error(Status::ServiceUnavailable, request).await;
+                response.set_raw_header("Connection", "close");
+                self.set_retry_after(&mut response);
+                response
+            }
+        };
 
         // Add a default 'Server' header if it isn't already there.
         // TODO: If removing Hyper, write out `Date` header too.
@@ -219,6 +361,11 @@ impl Rocket<Orbit> {
         // Run the response fairings.
         self.fairings.handle_response(request, &mut response).await;
 
+        // Report timings, including the total, if asked to.
+        if self.config.server_timing {
+            self.set_server_timing(request, &mut response);
+        }
+
         // Strip the body if this is a `HEAD` request.
         if was_head_request {
             response.strip_body();
@@ -227,25 +374,64 @@ impl Rocket<Orbit> {
         response
     }
 
+    /// Sets a `Server-Timing` header reporting the timings recorded for
+    /// `request` followed by the total time spent handling it.
+    fn set_server_timing(&self, request: &Request<'_>, response: &mut Response<'_>) {
+        let mut timings = request.timings();
+        timings.push(("total".into(), request.start_time().elapsed()));
+        let value = timings.iter()
+            .map(|(name, dur)| format!("{};dur={:.3}", name, dur.as_secs_f64() * 1000.0))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        response.set_raw_header("Server-Timing", value);
+    }
+
+    /// Sets a `Retry-After` header, as configured by `retry_after`, on a `503`
+    /// response Rocket generated, unless the response already has one.
+    fn set_retry_after(&self, response: &mut Response<'_>) {
+        let seconds = self.config.retry_after;
+        if seconds != 0
+            && response.status() == Status::ServiceUnavailable
+            && !response.headers().contains("Retry-After")
+        {
+            response.set_header(Header::new("Retry-After", seconds.to_string()));
+        }
+    }
+
+    /// Counts a request as in-flight, returning a guard that stops counting it
+    /// when dropped, or `None` if `max_concurrent_requests` requests are
+    /// already in-flight.
+    fn admit_request(&self) -> Option<InFlight<&AtomicUsize>> {
+        let max = self.config.max_concurrent_requests;
+        let in_flight = InFlight::new(&self.in_flight);
+        (max == 0 || in_flight.count <= max).then(|| in_flight)
+    }
+
     async fn route_and_process<'s, 'r: 's>(
         &'s self,
         request: &'r Request<'s>,
         data: Data<'r>
     ) -> Response<'r> {
-        let mut response = match self.route(request, data).await {
+        let mut response = match self.route(request, data, false).await {
             Outcome::Success(response) => response,
+            // Failures caused by a lost connection aren't the client's fault,
+            // and the response is never sent anyway: don't run any catchers.
+            Outcome::Forward(_) | Outcome::Failure(_) if request.body_aborted() => {
+                Response::new()
+            }
             Outcome::Forward(data) if request.method() == Method::Head => {
                 info_!("Autohandling {} request.", Paint::default("HEAD").bold());
 
                 // Dispatch the request again with Method `GET`.
                 request._set_method(Method::Get);
-                match self.route(request, data).await {
+                match self.route(request, data, true).await {
                     Outcome::Success(response) => response,
                     Outcome::Failure(status) => self.handle_error(status, request).await,
-                    Outcome::Forward(_) => self.handle_error(Status::NotFound, request).await,
+                    Outcome::Forward(_) => self.handle_unrouted(request).await,
                 }
             }
-            Outcome::Forward(_) => self.handle_error(Status::NotFound, request).await,
+            Outcome::Forward(_) => self.handle_unrouted(request).await,
             Outcome::Failure(status) => self.handle_error(status, request).await,
         };
 
@@ -259,24 +445,81 @@ impl Rocket<Orbit> {
         response
     }
 
+    /// Responds to a `request` that no route handled: with a `308` to the path
+    /// without its trailing slash if that would match a route and trailing
+    /// slashes are redirected, with a `405` carrying an `Allow` header if
+    /// routes match its path with other methods, or with a `404` otherwise.
+    async fn handle_unrouted<'s, 'r: 's>(&'s self, request: &'r Request<'s>) -> Response<'r> {
+        if self.config.trailing_slash == TrailingSlash::Redirect
+            && crate::router::has_trailing_slash(request)
+            && self.router.matches_without_slash(request)
+        {
+            let canonical = request.uri()
+                .map_path(|p| match p.as_str().trim_end_matches('/') {
+                    "" => "/".to_string(),
+                    path => path.to_string(),
+                })
+                .expect("removing a trailing slash from a valid path => valid path");
+
+            info_!("Redirecting to {} without trailing slash.", Paint::default(&canonical).bold());
+            return Response::build()
+                .status(Status::PermanentRedirect)
+                .raw_header("Location", canonical.to_string())
+                .finalize();
+        }
+
+        if request.traced_forwards().is_some() {
+            for route in self.router.method_mismatches(request) {
+                request.trace_forward(route, ForwardReason::MethodMismatch);
+            }
+        }
+
+        let allowed = self.router.allowed_methods(request);
+        if allowed.is_empty() || allowed.contains(&request.method()) {
+            return self.handle_error(Status::NotFound, request).await;
+        }
+
+        let allow = allowed.iter().map(|m| m.as_str()).collect::<Vec<_>>().join(", ");
+        info_!("Path only matched routes for {}.", Paint::default(&allow).bold());
+        let mut response = self.handle_error(Status::MethodNotAllowed, request).await;
+        response.set_raw_header("Allow", allow);
+        response
+    }
+
     /// Tries to find a `Responder` for a given `request`. It does this by
     /// routing the request and calling the handler for each matching route
     /// until one of the handlers returns success or failure, or there are no
     /// additional routes to try (forward). The corresponding outcome for each
-    /// condition is returned.
+    /// condition is returned. If `autohandling_head`, the request is a `HEAD`
+    /// request being routed as `GET`, and routes that opt out are skipped.
     #[inline]
     async fn route<'s, 'r: 's>(
         &'s self,
         request: &'r Request<'s>,
         mut data: Data<'r>,
+        autohandling_head: bool,
     ) -> route::Outcome<'r> {
+        // Expose the default limit for the body's content type.
+        data.default_limit = request.limits().for_content_type(request.content_type());
+        data.budget = self.body_budget.clone();
+
         // Go through the list of matching routes until we fail or succeed.
         for route in self.router.route(request) {
+            if autohandling_head && !route.auto_head {
+                info_!("Skipping {}: `HEAD` autohandling disabled.", route);
+                request.trace_forward(route, ForwardReason::AutoHeadDisabled);
+                continue;
+            }
+
             // Retrieve and set the requests parameters.
             info_!("Matched: {}", route);
             request.set_route(route);
 
+            // Expose the route's data limit, if any, to its data guard.
+            data.limit = route.limit.as_ref().and_then(|name| request.limits().get(name));
+
             let name = route.name.as_deref();
+            let traced = request.traced_forwards();
             let outcome = handle(name, || route.handler.handle(request, data)).await
                 .unwrap_or(Outcome::Failure(Status::InternalServerError));
 
@@ -286,11 +529,18 @@ impl Rocket<Orbit> {
             info_!("{} {}", Paint::default("Outcome:").bold(), outcome);
             match outcome {
                 o@Outcome::Success(_) | o@Outcome::Failure(_) => return o,
-                Outcome::Forward(unused_data) => data = unused_data,
+                Outcome::Forward(unused_data) => {
+                    if request.traced_forwards() == traced {
+                        request.trace_forward(route, ForwardReason::Handler);
+                    }
+
+                    data = unused_data;
+                }
             }
         }
 
         error_!("No matching routes for {}.", request);
+        request.clear_route();
         Outcome::Forward(data)
     }
 
@@ -329,20 +579,40 @@ impl Rocket<Orbit> {
 
     // Invokes the catcher for `status`. Returns the response on success.
     //
-    // On catcher failure, the 500 error catcher is attempted. If _that_ fails,
-    // the (infallible) default 500 error cather is used.
+    // If the catcher forwards to another status via `catcher::Forward`, the
+    // catcher for that status is invoked instead, unless it was already
+    // invoked for this request. On catcher failure, the 500 error catcher is
+    // attempted. If _that_ fails, the (infallible) default 500 error cather is
+    // used.
     pub(crate) async fn handle_error<'s, 'r: 's>(
         &'s self,
         mut status: Status,
         req: &'r Request<'s>
     ) -> Response<'r> {
-        // Dispatch to the `status` catcher.
-        if let Ok(r) = self.invoke_catcher(status, req).await {
-            return r;
+        // Dispatch to the `status` catcher, following any forwards.
+        let mut attempted = vec![];
+        loop {
+            req.take_catcher_forward();
+            attempted.push(status);
+            if let Ok(r) = self.invoke_catcher(status, req).await {
+                return r;
+            }
+
+            match req.take_catcher_forward() {
+                Some(next) if !attempted.contains(&next) => {
+                    warn_!("Catcher forwarded to {} catcher.", Paint::blue(next.code).bold());
+                    status = next;
+                }
+                Some(next) => {
+                    error_!("Catcher forwarded to {} catcher, which already ran.", next.code);
+                    break;
+                }
+                None => break,
+            }
         }
 
         // If it fails and it's not a 500, try the 500 catcher.
-        if status != Status::InternalServerError {
+        if !attempted.contains(&Status::InternalServerError) {
             error_!("Catcher failed. Attemping 500 error catcher.");
             status = Status::InternalServerError;
             if let Ok(r) = self.invoke_catcher(status, req).await {
@@ -389,15 +659,14 @@ impl Rocket<Orbit> {
         self.http_server(l).await
     }
 
-    // TODO.async: Solidify the Listener APIs and make this function public
     pub(crate) async fn http_server<L>(self, listener: L) -> Result<Self, Error>
         where L: Listener + Send, <L as Listener>::Connection: Send + Unpin + 'static
     {
         // Emit a warning if we're not running inside of Rocket's async runtime.
         if self.config.profile == Config::DEBUG_PROFILE {
-            tokio::task::spawn_blocking(|| {
-                let this  = std::thread::current();
-                if !this.name().map_or(false, |s| s.starts_with("rocket-worker")) {
+            let config = self.config.clone();
+            tokio::task::spawn_blocking(move || {
+                if !config.in_worker_thread() {
                     warn!("Rocket is executing inside of a custom runtime.");
                     info_!("Rocket's runtime is enabled via `#[rocket::main]` or `#[launch]`.");
                     info_!("Forced shutdown is disabled. Runtime settings may be suboptimal.");
@@ -413,6 +682,7 @@ impl Rocket<Orbit> {
         let sig_stream = self.config.shutdown.signal_stream();
         let grace = self.config.shutdown.grace as u64;
         let mercy = self.config.shutdown.mercy as u64;
+        let idle_timeout = self.config.idle_timeout as u64;
 
         // Start a task that listens for external signals and notifies shutdown.
         if let Some(mut stream) = sig_stream {
@@ -430,8 +700,10 @@ impl Rocket<Orbit> {
             });
         }
 
-        // Save the keep-alive value for later use; we're about to move `self`.
+        // Save the keep-alive and header size values for later use; we're about
+        // to move `self`. Hyper refuses to buffer less than 8KiB of the head.
         let keep_alive = self.config.keep_alive;
+        let max_buf_size = std::cmp::max(self.config.max_header_size.as_u64(), 8192);
 
         // Create the Hyper `Service`.
         let rocket = Arc::new(self);
@@ -442,15 +714,18 @@ impl Rocket<Orbit> {
                 client_certificates: conn.peer_certificates(),
             };
 
+            let in_flight = conn.in_flight();
             async move {
                 Ok::<_, std::convert::Infallible>(hyper::service::service_fn(move |req| {
-                    hyper_service_fn(rocket.clone(), connection.clone(), req)
+                    hyper_service_fn(rocket.clone(), connection.clone(), in_flight.clone(), req)
                 }))
             }
         };
 
         // NOTE: `hyper` uses `tokio::spawn()` as the default executor.
-        let listener = CancellableListener::new(shutdown.clone(), listener, grace, mercy);
+        let listener = CancellableListener::new(
+            shutdown.clone(), listener, grace, mercy, idle_timeout
+        );
         let builder = hyper::server::Server::builder(Incoming::new(listener).nodelay(true));
 
         #[cfg(feature = "http2")]
@@ -462,6 +737,7 @@ impl Rocket<Orbit> {
         let server = builder
             .http1_keepalive(keep_alive != 0)
             .http1_preserve_header_case(true)
+            .http1_max_buf_size(max_buf_size as usize)
             .serve(hyper::service::make_service_fn(service_fn))
             .with_graceful_shutdown(shutdown.clone());
 
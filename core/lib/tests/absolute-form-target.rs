@@ -0,0 +1,33 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use rocket::http::uri::Host;
+
+#[get("/hello/<name>")]
+fn hello(name: &str, host: Option<&Host<'_>>) -> String {
+    format!("{} {:?}", name, host.map(|h| h.to_string()))
+}
+
+mod absolute_form_target_tests {
+    use super::*;
+
+    async fn send(port: u16, request: &str) -> String {
+        let response = common::send(port, request).await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{:?}", response);
+        response.split("\r\n\r\n").nth(1).unwrap().to_string()
+    }
+
+    #[rocket::async_test]
+    async fn absolute_form_routes_on_path_and_exposes_authority() {
+        let port = common::launch(rocket::build().mount("/", routes![hello])).await;
+
+        let body = send(port, "GET http://rocket.rs:8080/hello/bob?x=y HTTP/1.1\r\n\
+            Host: localhost\r\nConnection: close\r\n\r\n").await;
+        assert_eq!(body, r#"bob Some("rocket.rs:8080")"#);
+
+        let body = send(port, "GET /hello/bob HTTP/1.1\r\n\
+            Host: localhost\r\nConnection: close\r\n\r\n").await;
+        assert_eq!(body, r#"bob Some("localhost")"#);
+    }
+}
@@ -0,0 +1,100 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::State;
+use rocket::http::Status;
+use rocket::tokio::sync::Notify;
+
+#[derive(Default)]
+struct Observed {
+    errors: Mutex<Vec<io::ErrorKind>>,
+    catcher_calls: AtomicUsize,
+    done: Notify,
+}
+
+#[post("/string", data = "<body>")]
+fn string(body: String) -> String {
+    body
+}
+
+#[post("/result", data = "<body>")]
+fn result(body: Result<String, io::Error>, observed: &State<Arc<Observed>>) -> Status {
+    match body {
+        Ok(_) => Status::Ok,
+        Err(e) => {
+            observed.errors.lock().unwrap().push(e.kind());
+            Status::BadRequest
+        }
+    }
+}
+
+#[catch(default)]
+fn catcher(req: &rocket::Request<'_>) -> &'static str {
+    req.rocket().state::<Arc<Observed>>().unwrap().catcher_calls.fetch_add(1, Ordering::SeqCst);
+    "caught"
+}
+
+mod body_read_aborted_tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use rocket::fairing::AdHoc;
+    use rocket::tokio::time::timeout;
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn launch() -> (u16, Arc<Observed>) {
+        let observed = Arc::new(Observed::default());
+        let rocket = rocket::build()
+            .manage(observed.clone())
+            .mount("/", routes![string, result])
+            .register("/", catchers![catcher])
+            .attach(AdHoc::on_response("Notify Done", |req, _| Box::pin(async move {
+                req.rocket().state::<Arc<Observed>>().unwrap().done.notify_one();
+            })));
+
+        (common::launch(rocket).await, observed)
+    }
+
+    /// Sends a request whose body is cut short, then disconnects. Returns
+    /// whatever the server wrote back before closing the connection.
+    async fn send_partial(port: u16, path: &str) -> Vec<u8> {
+        let mut stream = common::connect(port).await;
+        let head = format!("POST {} HTTP/1.1\r\nHost: localhost\r\n\
+            Content-Length: 1000\r\n\r\nonly a bit of the body", path);
+
+        stream.write_all(head.as_bytes()).await.unwrap();
+        stream.shutdown().await.unwrap();
+
+        let mut response = vec![];
+        let read = timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+        read.expect("connection closed").unwrap_or(0);
+        response
+    }
+
+    #[rocket::async_test]
+    async fn disconnect_is_distinguishable_from_invalid_data() {
+        let (port, observed) = launch().await;
+        let response = send_partial(port, "/result").await;
+        assert!(response.is_empty(), "{:?}", String::from_utf8_lossy(&response));
+
+        observed.done.notified().await;
+        let errors = observed.errors.lock().unwrap().clone();
+        assert_eq!(errors, &[io::ErrorKind::ConnectionAborted]);
+    }
+
+    #[rocket::async_test]
+    async fn disconnect_does_not_produce_400() {
+        let (port, observed) = launch().await;
+        let response = send_partial(port, "/string").await;
+        assert!(response.is_empty(), "{:?}", String::from_utf8_lossy(&response));
+
+        observed.done.notified().await;
+        assert_eq!(observed.catcher_calls.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,60 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::catcher::Forward;
+use rocket::http::Status;
+
+#[catch(404)]
+fn not_found(req: &Request) -> Result<&'static str, Forward> {
+    match req.uri().path().as_str() {
+        "/admin" => Err(Forward(Status::Unauthorized)),
+        "/loop" => Err(Forward(Status::Forbidden)),
+        _ => Ok("not found"),
+    }
+}
+
+#[catch(401)]
+fn unauthorized(status: Status, _: &Request) -> String {
+    format!("{}: please log in", status.code)
+}
+
+#[catch(403)]
+fn forbidden() -> Forward {
+    Forward(Status::NotFound)
+}
+
+#[catch(500)]
+fn internal_error() -> &'static str {
+    "internal error"
+}
+
+mod catcher_forward_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let catchers = catchers![not_found, unauthorized, forbidden, internal_error];
+        Client::debug(rocket::build().register("/", catchers)).unwrap()
+    }
+
+    #[test]
+    fn catcher_forwards_to_other_status() {
+        let client = client();
+        let response = client.get("/admin").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(response.into_string().unwrap(), "401: please log in");
+
+        let response = client.get("/other").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.into_string().unwrap(), "not found");
+    }
+
+    #[test]
+    fn catcher_forward_loops_fail() {
+        let client = client();
+        let response = client.get("/loop").dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+        assert_eq!(response.into_string().unwrap(), "internal error");
+    }
+}
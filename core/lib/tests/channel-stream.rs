@@ -0,0 +1,72 @@
+#[macro_use] extern crate rocket;
+
+use std::io;
+
+use rocket::http::ContentType;
+use rocket::response::stream::ChannelStream;
+use rocket::tokio::{self, sync::mpsc};
+
+#[get("/chunks")]
+fn chunks() -> ChannelStream {
+    let (tx, rx) = mpsc::channel(1);
+    tokio::spawn(async move {
+        for chunk in ["one,", "two,", "three"] {
+            tx.send(chunk).await.unwrap();
+        }
+    });
+
+    ChannelStream::new(rx).content_type(ContentType::Plain)
+}
+
+#[get("/bytes")]
+fn bytes() -> ChannelStream {
+    let (tx, rx) = mpsc::channel(1);
+    tokio::spawn(async move {
+        tx.send(vec![1u8, 2]).await.unwrap();
+        tx.send(vec![3u8]).await.unwrap();
+    });
+
+    ChannelStream::from(rx)
+}
+
+#[get("/error")]
+fn error() -> ChannelStream {
+    let (tx, rx) = mpsc::channel(1);
+    tokio::spawn(async move {
+        tx.send(Ok("partial")).await.unwrap();
+        tx.send(Err(io::Error::new(io::ErrorKind::Other, "oh no"))).await.unwrap();
+        let _ = tx.send(Ok("never")).await;
+    });
+
+    ChannelStream::fallible(rx)
+}
+
+mod channel_stream_tests {
+    use super::*;
+
+    use rocket::local::asynchronous::Client;
+
+    #[rocket::async_test]
+    async fn channel_stream_sends_every_chunk() {
+        let rocket = rocket::build().mount("/", routes![chunks, bytes, error]);
+        let client = Client::debug(rocket).await.unwrap();
+
+        let response = client.get("/chunks").dispatch().await;
+        assert_eq!(response.content_type(), Some(ContentType::Plain));
+        assert_eq!(response.into_string().await.unwrap(), "one,two,three");
+
+        let response = client.get("/bytes").dispatch().await;
+        assert_eq!(response.content_type(), Some(ContentType::Binary));
+        assert_eq!(response.into_bytes().await.unwrap(), [1, 2, 3]);
+    }
+
+    #[rocket::async_test]
+    async fn channel_stream_ends_on_error() {
+        let rocket = rocket::build().mount("/", routes![error]);
+        let client = Client::debug(rocket).await.unwrap();
+
+        let response = client.get("/error").dispatch().await;
+        let body = response.into_string().await;
+        assert!(body.map_or(true, |body| !body.contains("never")));
+    }
+}
@@ -0,0 +1,49 @@
+//! Helpers for tests that talk to a launched Rocket over TCP.
+
+// Each test crate uses only some of these.
+#![allow(dead_code)]
+
+use rocket::{Rocket, Build, Config};
+use rocket::fairing::AdHoc;
+use rocket::futures::channel::oneshot;
+use rocket::tokio::{self, net::TcpStream};
+use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Launches `rocket` in the background on a port chosen by the OS and returns
+/// the port once the server is listening.
+pub async fn launch(rocket: Rocket<Build>) -> u16 {
+    let (tx, rx) = oneshot::channel();
+    let figment = rocket.figment().clone().merge((Config::PORT, 0));
+    let rocket = rocket.configure(figment)
+        .attach(AdHoc::on_liftoff("Send Port -> Channel", move |rocket| {
+            Box::pin(async move {
+                tx.send(rocket.config().port).unwrap();
+            })
+        }));
+
+    tokio::spawn(rocket.launch());
+    rx.await.unwrap()
+}
+
+/// Opens a new connection to the server listening on `port`.
+pub async fn connect(port: u16) -> TcpStream {
+    TcpStream::connect(("127.0.0.1", port)).await.unwrap()
+}
+
+/// Writes `request` to a new connection to the server listening on `port`
+/// and returns everything read back until the server closes the connection.
+pub async fn send(port: u16, request: &str) -> String {
+    let mut stream = connect(port).await;
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+/// Sends a `GET` request for `target` with `Connection: close` to the server
+/// listening on `port` and returns the full response.
+pub async fn get(port: u16, target: &str) -> String {
+    let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n", target);
+    send(port, &request).await
+}
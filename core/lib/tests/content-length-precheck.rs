@@ -0,0 +1,88 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use rocket::data::Capped;
+use rocket::form::Form;
+
+#[post("/string", data = "<body>", limit = "upload")]
+fn string(body: String) -> String {
+    body
+}
+
+#[post("/capped", data = "<body>", limit = "upload")]
+fn capped(body: Capped<String>) -> String {
+    format!("{}:{}", body.is_complete(), body.into_inner())
+}
+
+#[post("/form", data = "<body>", limit = "upload")]
+fn form(body: Form<&str>) -> String {
+    body.to_string()
+}
+
+mod content_length_precheck_tests {
+    use super::*;
+
+    use rocket::Config;
+    use rocket::data::{Limits, ToByteUnit};
+    use rocket::local::blocking::Client;
+    use rocket::http::{ContentType, Header, Status};
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn rocket() -> rocket::Rocket<rocket::Build> {
+        let limits = Limits::default().limit("upload", 16.bytes());
+        let config = Config { limits, ..Config::debug_default() };
+        rocket::custom(config).mount("/", routes![string, capped, form])
+    }
+
+    fn post(client: &Client, path: &str, body: &str, len: usize) -> (Status, Option<String>) {
+        let content_type = match path {
+            "/form" => ContentType::Form,
+            _ => ContentType::Plain,
+        };
+
+        let response = client.post(path)
+            .header(content_type)
+            .header(Header::new("Content-Length", len.to_string()))
+            .body(body)
+            .dispatch();
+
+        (response.status(), response.into_string())
+    }
+
+    #[test]
+    fn declared_over_limit_is_rejected() {
+        let client = Client::debug(rocket()).unwrap();
+        for &(path, body) in &[("/string", "hello"), ("/form", "a=b")] {
+            let (status, _) = post(&client, path, body, 1_000_000);
+            assert_eq!(status, Status::PayloadTooLarge, "{}", path);
+
+            let (status, _) = post(&client, path, body, body.len());
+            assert_eq!(status, Status::Ok, "{}", path);
+        }
+    }
+
+    #[test]
+    fn capped_guards_still_read() {
+        let client = Client::debug(rocket()).unwrap();
+        let (status, body) = post(&client, "/capped", "hello", 1_000_000);
+        assert_eq!(status, Status::Ok);
+        assert_eq!(body.unwrap(), "true:hello");
+    }
+
+    #[rocket::async_test]
+    async fn body_is_never_read() {
+        let port = common::launch(rocket()).await;
+
+        // Send only the head. If the server waited on the body, no response
+        // would ever arrive.
+        let mut stream = common::connect(port).await;
+        stream.write_all(b"POST /string HTTP/1.1\r\n\
+            Host: localhost\r\n\
+            Content-Length: 1000000\r\n\r\n").await.unwrap();
+
+        let mut buf = [0u8; 12];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"HTTP/1.1 413");
+    }
+}
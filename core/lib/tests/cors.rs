@@ -0,0 +1,151 @@
+#[macro_use] extern crate rocket;
+
+use rocket::cors::Cors;
+use rocket::http::Method;
+use rocket::time::Duration;
+
+#[post("/items")]
+fn create() -> &'static str {
+    "created"
+}
+
+#[get("/items")]
+fn list() -> &'static str {
+    "items"
+}
+
+#[get("/status")]
+fn status() -> &'static str {
+    "ok"
+}
+
+mod cors_tests {
+    use super::*;
+
+    use rocket::http::{Header, Status};
+    use rocket::local::blocking::Client;
+
+    fn client(cors: Cors) -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![create, list, status])
+            .attach(cors);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    fn cors() -> Cors {
+        Cors::new()
+            .allow_origin("https://rocket.rs")
+            .allow_origin("https://*.rocket.rs")
+            .allow_methods([Method::Get, Method::Post])
+            .allow_headers(["Content-Type"])
+            .max_age(Duration::minutes(10))
+    }
+
+    #[test]
+    fn successful_preflight() {
+        let client = client(cors());
+        let response = client.options("/items")
+            .header(Header::new("Origin", "https://api.rocket.rs"))
+            .header(Header::new("Access-Control-Request-Method", "POST"))
+            .header(Header::new("Access-Control-Request-Headers", "content-type"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NoContent);
+        let headers = response.headers();
+        assert_eq!(headers.get_one("Access-Control-Allow-Origin"), Some("https://api.rocket.rs"));
+        assert_eq!(headers.get_one("Access-Control-Allow-Methods"), Some("GET, POST"));
+        assert_eq!(headers.get_one("Access-Control-Allow-Headers"), Some("Content-Type"));
+        assert_eq!(headers.get_one("Access-Control-Max-Age"), Some("600"));
+        assert_eq!(headers.get_one("Vary"), Some("Origin"));
+        assert!(headers.get_one("Access-Control-Allow-Credentials").is_none());
+    }
+
+    #[test]
+    fn disallowed_origin() {
+        let client = client(cors());
+        let response = client.options("/items")
+            .header(Header::new("Origin", "https://evil.com"))
+            .header(Header::new("Access-Control-Request-Method", "POST"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Forbidden);
+        assert!(response.headers().get_one("Access-Control-Allow-Origin").is_none());
+
+        let response = client.get("/items")
+            .header(Header::new("Origin", "https://evil.com"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.headers().get_one("Access-Control-Allow-Origin").is_none());
+        assert_eq!(response.headers().get_one("Vary"), Some("Origin"));
+
+        let response = client.get("/items").dispatch();
+        assert!(response.headers().get_one("Access-Control-Allow-Origin").is_none());
+        assert_eq!(response.headers().get_one("Vary"), Some("Origin"));
+    }
+
+    #[test]
+    fn disallowed_method_or_header() {
+        let client = client(cors());
+        let response = client.options("/items")
+            .header(Header::new("Origin", "https://rocket.rs"))
+            .header(Header::new("Access-Control-Request-Method", "DELETE"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client.options("/items")
+            .header(Header::new("Origin", "https://rocket.rs"))
+            .header(Header::new("Access-Control-Request-Method", "POST"))
+            .header(Header::new("Access-Control-Request-Headers", "X-Secret"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[test]
+    fn actual_request_gets_allow_origin() {
+        let client = client(cors().allow_credentials(true));
+        let response = client.post("/items")
+            .header(Header::new("Origin", "https://rocket.rs"))
+            .dispatch();
+
+        let headers = response.headers();
+        assert_eq!(headers.get_one("Access-Control-Allow-Origin"), Some("https://rocket.rs"));
+        assert_eq!(headers.get_one("Access-Control-Allow-Credentials"), Some("true"));
+        assert!(headers.get_one("Access-Control-Allow-Methods").is_none());
+        assert_eq!(response.into_string().unwrap(), "created");
+    }
+
+    #[test]
+    fn any_origin_is_wildcard() {
+        let client = client(Cors::new().allow_any_origin());
+        let response = client.get("/items")
+            .header(Header::new("Origin", "https://example.com"))
+            .dispatch();
+
+        assert_eq!(response.headers().get_one("Access-Control-Allow-Origin"), Some("*"));
+        assert!(response.headers().get_one("Vary").is_none());
+    }
+
+    #[test]
+    fn non_preflight_options_is_not_answered() {
+        let client = client(cors());
+        let response = client.options("/items")
+            .header(Header::new("Origin", "https://rocket.rs"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("GET, POST, HEAD"));
+        assert!(response.headers().get_one("Access-Control-Allow-Methods").is_none());
+    }
+
+    #[test]
+    fn preflight_route_is_not_advertised() {
+        let client = client(cors());
+        let response = client.post("/status").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD"));
+    }
+}
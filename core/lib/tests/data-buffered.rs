@@ -0,0 +1,44 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Data};
+use rocket::fairing::{Fairing, Info, Kind};
+
+struct Peeker;
+
+#[rocket::async_trait]
+impl Fairing for Peeker {
+    fn info(&self) -> Info {
+        Info { name: "Peeker", kind: Kind::Request }
+    }
+
+    async fn on_request(&self, _: &mut Request<'_>, data: &mut Data<'_>) {
+        assert_eq!(data.peek(10).await, b"0123456789");
+        assert!(data.buffered().starts_with(b"0123456789"));
+    }
+}
+
+#[post("/", data = "<data>")]
+fn index(data: Data<'_>) -> String {
+    let buffered = std::str::from_utf8(data.buffered()).unwrap();
+    format!("{}:{}", data.buffered_len(), buffered)
+}
+
+mod data_buffered_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+
+    #[test]
+    fn buffered_reflects_peeked_data() {
+        let rocket = rocket::build().mount("/", routes![index]).attach(Peeker);
+        let client = Client::debug(rocket).unwrap();
+        let response = client.post("/").body("0123456789abcdef").dispatch();
+
+        // The handler sees what the fairing buffered without reading more.
+        let body = response.into_string().unwrap();
+        let (len, buffered) = body.split_once(':').unwrap();
+        assert_eq!(len.parse::<usize>().unwrap(), buffered.len());
+        assert!(buffered.starts_with("0123456789"), "{:?}", buffered);
+        assert!("0123456789abcdef".starts_with(buffered));
+    }
+}
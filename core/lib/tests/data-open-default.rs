@@ -0,0 +1,75 @@
+#[macro_use] extern crate rocket;
+
+use rocket::data::{Data, ToByteUnit};
+
+#[post("/default", data = "<data>")]
+async fn default(data: Data<'_>) -> std::io::Result<String> {
+    let bytes = data.open_default().into_bytes().await?;
+    Ok(format!("{}:{}", bytes.len(), bytes.is_complete()))
+}
+
+#[post("/capped", data = "<data>")]
+async fn capped(data: Data<'_>) -> std::io::Result<String> {
+    let bytes = data.open_capped(4.bytes()).await?;
+    Ok(format!("{}:{}", bytes.len(), bytes.is_complete()))
+}
+
+#[post("/route", data = "<data>", limit = "small")]
+async fn route(data: Data<'_>) -> std::io::Result<String> {
+    let bytes = data.open_default().into_bytes().await?;
+    Ok(format!("{}:{}", bytes.len(), bytes.is_complete()))
+}
+
+mod data_open_default_tests {
+    use super::*;
+
+    use rocket::{Config, Rocket, Build};
+    use rocket::data::Limits;
+    use rocket::http::ContentType;
+    use rocket::local::blocking::Client;
+
+    fn rocket() -> Rocket<Build> {
+        let limits = Limits::default()
+            .limit("json", 16.bytes())
+            .limit("form", 8.bytes())
+            .limit("bytes", 6.bytes())
+            .limit("small", 2.bytes());
+
+        let config = Config { limits, ..Config::debug_default() };
+        rocket::custom(config).mount("/", routes![default, capped, route])
+    }
+
+    #[test]
+    fn default_limit_follows_content_type() {
+        let client = Client::debug(rocket()).unwrap();
+        let body = "0123456789ab";
+
+        let response = client.post("/default").header(ContentType::JSON).body(body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "12:true");
+
+        let response = client.post("/default").header(ContentType::Form).body(body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "8:false");
+
+        let response = client.post("/default").body(body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "6:false");
+    }
+
+    #[test]
+    fn route_limit_takes_precedence() {
+        let client = Client::debug(rocket()).unwrap();
+        let response = client.post("/route").header(ContentType::JSON).body("0123").dispatch();
+        assert_eq!(response.into_string().unwrap(), "2:false");
+    }
+
+    #[test]
+    fn capped_uses_lesser_limit() {
+        let client = Client::debug(rocket()).unwrap();
+        let body = "0123456789ab";
+
+        let response = client.post("/capped").header(ContentType::JSON).body(body).dispatch();
+        assert_eq!(response.into_string().unwrap(), "4:false");
+
+        let response = client.post("/capped").header(ContentType::JSON).body("012").dispatch();
+        assert_eq!(response.into_string().unwrap(), "3:true");
+    }
+}
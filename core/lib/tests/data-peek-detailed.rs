@@ -0,0 +1,53 @@
+use rocket::data::{Data, PEEK_BYTES};
+
+fn data(body: &'static [u8]) -> Data<'static> {
+    Data::from_reader(body)
+}
+
+#[rocket::async_test]
+async fn body_shorter_than_requested() {
+    let mut data = data(b"hi");
+    let peek = data.peek_detailed(5).await;
+    assert_eq!(peek.bytes(), b"hi");
+    assert!(peek.is_eof());
+    assert!(!peek.is_capped());
+}
+
+#[rocket::async_test]
+async fn body_hitting_the_cap() {
+    static BODY: [u8; 2 * PEEK_BYTES] = [7; 2 * PEEK_BYTES];
+
+    let mut data = data(&BODY);
+    let peek = data.peek_detailed(BODY.len()).await;
+    assert_eq!(peek.bytes(), &BODY[..PEEK_BYTES]);
+    assert!(!peek.is_eof());
+    assert!(peek.is_capped());
+
+    // Requesting exactly the cap isn't capped.
+    let peek = data.peek_detailed(PEEK_BYTES).await;
+    assert_eq!(peek.bytes().len(), PEEK_BYTES);
+    assert!(!peek.is_capped());
+}
+
+#[rocket::async_test]
+async fn body_of_exact_length() {
+    let mut data = data(b"hello");
+    let peek = data.peek_detailed(5).await;
+    assert_eq!(peek.bytes(), b"hello");
+    assert!(!peek.is_capped());
+
+    // Only a longer peek can discover that the body ends here.
+    let peek = data.peek_detailed(6).await;
+    assert_eq!(peek.bytes(), b"hello");
+    assert!(peek.is_eof());
+    assert!(!peek.is_capped());
+}
+
+#[rocket::async_test]
+async fn complete_body_under_cap_is_not_capped() {
+    let mut data = Data::from_bytes(b"short and complete".to_vec());
+    let peek = data.peek_detailed(PEEK_BYTES + 1).await;
+    assert_eq!(peek.bytes(), b"short and complete");
+    assert!(peek.is_eof());
+    assert!(!peek.is_capped());
+}
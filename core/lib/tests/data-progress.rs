@@ -0,0 +1,55 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::{Arc, Mutex};
+
+use rocket::data::{Data, ToByteUnit};
+
+#[post("/", data = "<data>")]
+async fn upload(data: Data<'_>) -> String {
+    let calls = Arc::new(Mutex::new(vec![]));
+    let progress = calls.clone();
+    let bytes = data.open_with_progress(1.mebibytes(), move |read, total| {
+        progress.lock().unwrap().push((read, total));
+    });
+
+    let bytes = bytes.into_bytes().await.unwrap();
+    let calls = calls.lock().unwrap();
+    assert!(calls.windows(2).all(|w| w[0].0 < w[1].0));
+    assert_eq!(calls.last().map_or(0, |c| c.0), bytes.len() as u64);
+
+    let total = calls.first().and_then(|c| c.1);
+    format!("{} {} {:?}", bytes.len(), calls.len(), total)
+}
+
+mod data_progress_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+    use rocket::http::Header;
+
+    #[test]
+    fn progress_sums_to_body_length() {
+        let client = Client::debug_with(routes![upload]).unwrap();
+        let body = vec![b'a'; 64 * 1024];
+        let response = client.post("/")
+            .header(Header::new("Content-Length", body.len().to_string()))
+            .body(&body)
+            .dispatch();
+
+        let string = response.into_string().unwrap();
+        let parts: Vec<_> = string.split(' ').collect();
+        assert_eq!(parts[0], "65536");
+        assert!(parts[1].parse::<usize>().unwrap() > 1);
+        assert_eq!(parts[2], "Some(65536)");
+    }
+
+    #[test]
+    fn progress_without_declared_length() {
+        let client = Client::debug_with(routes![upload]).unwrap();
+        let response = client.post("/").body("hello").dispatch();
+        assert_eq!(response.into_string().unwrap(), "5 1 None");
+
+        let response = client.post("/").dispatch();
+        assert_eq!(response.into_string().unwrap(), "0 0 None");
+    }
+}
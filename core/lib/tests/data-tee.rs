@@ -0,0 +1,109 @@
+#[macro_use] extern crate rocket;
+
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use rocket::{Request, Data};
+use rocket::data::ByteUnit;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::tokio::io::AsyncWrite;
+
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl AsyncWrite for Capture {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        // Accept at most 3 bytes at a time to exercise partial writes.
+        let n = std::cmp::min(buf.len(), 3);
+        self.0.lock().unwrap().extend_from_slice(&buf[..n]);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+struct Tee {
+    capture: Capture,
+    cap: ByteUnit,
+}
+
+#[rocket::async_trait]
+impl Fairing for Tee {
+    fn info(&self) -> Info {
+        Info { name: "Tee", kind: Kind::Request }
+    }
+
+    async fn on_request(&self, _: &mut Request<'_>, data: &mut Data<'_>) {
+        data.tee(self.capture.clone(), self.cap);
+    }
+}
+
+#[post("/", data = "<body>")]
+fn echo(body: String) -> String {
+    body
+}
+
+#[post("/capped", data = "<data>")]
+async fn capped(data: Data<'_>) -> String {
+    let read = Arc::new(Mutex::new(0));
+    let progress = read.clone();
+    let stream = data.open_with_progress(ByteUnit::from(10), move |n, _| {
+        *progress.lock().unwrap() = n;
+    });
+
+    let bytes = stream.into_bytes().await.unwrap();
+    let read = *read.lock().unwrap();
+    format!("{} {} {}", bytes.len(), bytes.is_complete(), read)
+}
+
+mod data_tee_tests {
+    use super::*;
+
+    use rocket::data::ToByteUnit;
+    use rocket::local::blocking::Client;
+
+    const BODY: &str = "the quick brown fox jumps over the lazy dog";
+
+    fn client(capture: &Capture, cap: ByteUnit) -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![echo, capped])
+            .attach(Tee { capture: capture.clone(), cap });
+
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn tee_captures_body_read_by_guard() {
+        let capture = Capture::default();
+        let client = client(&capture, 1.kibibytes());
+        let response = client.post("/").body(BODY).dispatch();
+        assert_eq!(response.into_string().unwrap(), BODY);
+        assert_eq!(&*capture.0.lock().unwrap(), BODY.as_bytes());
+    }
+
+    #[test]
+    fn tee_is_capped() {
+        let capture = Capture::default();
+        let client = client(&capture, 9.bytes());
+        let response = client.post("/").body(BODY).dispatch();
+        assert_eq!(response.into_string().unwrap(), BODY);
+        assert_eq!(&*capture.0.lock().unwrap(), &BODY.as_bytes()[..9]);
+    }
+
+    #[test]
+    fn over_limit_probe_is_not_mirrored_or_reported() {
+        let capture = Capture::default();
+        let client = client(&capture, 1.kibibytes());
+        let response = client.post("/capped").body(BODY).dispatch();
+        assert_eq!(response.into_string().unwrap(), "10 false 10");
+        assert_eq!(&*capture.0.lock().unwrap(), &BODY.as_bytes()[..10]);
+    }
+}
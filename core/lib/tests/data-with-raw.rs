@@ -0,0 +1,93 @@
+#[macro_use] extern crate rocket;
+
+use rocket::data::WithRaw;
+use rocket::form::Form;
+
+#[derive(FromForm)]
+struct Event<'r> {
+    kind: &'r str,
+    id: usize,
+}
+
+#[post("/form", data = "<event>")]
+fn form(event: WithRaw<Form<Event<'_>>>) -> String {
+    let raw = std::str::from_utf8(event.raw_bytes()).unwrap();
+    format!("{} {} {}", event.kind, event.id, raw)
+}
+
+#[post("/string", data = "<body>")]
+fn string(body: WithRaw<String>) -> String {
+    assert_eq!(body.raw_bytes(), body.as_bytes());
+    body.into_inner()
+}
+
+#[post("/plain", data = "<body>")]
+fn plain(body: String) -> String {
+    body
+}
+
+mod data_with_raw_tests {
+    use super::*;
+
+    use rocket::{Config, Build, Rocket};
+    use rocket::local::blocking::Client;
+    use rocket::data::{Limits, ToByteUnit};
+    use rocket::http::{ContentType, Header, Status};
+
+    fn rocket() -> Rocket<Build> {
+        let limits = Limits::default().limit("string", 8.bytes());
+        let config = Config { limits, ..Config::debug_default() };
+        rocket::custom(config).mount("/", routes![form, string, plain])
+    }
+
+    #[test]
+    fn with_raw_keeps_raw_body_and_value() {
+        let client = Client::debug(rocket()).unwrap();
+        let response = client.post("/form")
+            .header(ContentType::Form)
+            .body("kind=ping&id=7")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "ping 7 kind=ping&id=7");
+
+        let response = client.post("/string").body("hello").dispatch();
+        assert_eq!(response.into_string().unwrap(), "hello");
+    }
+
+    #[test]
+    fn with_raw_propagates_inner_failure() {
+        let client = Client::debug(rocket()).unwrap();
+        let response = client.post("/form")
+            .header(ContentType::Form)
+            .body("kind=ping&id=seven")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+    }
+
+    #[test]
+    fn with_raw_enforces_limit() {
+        let client = Client::debug(rocket()).unwrap();
+        let body = "too long for the limit";
+
+        // Without a declared length, the limit is only hit while reading.
+        let response = client.post("/string").body(body).dispatch();
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+
+        let response = client.post("/string")
+            .header(Header::new("Content-Length", body.len().to_string()))
+            .body(body)
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+    }
+
+    #[test]
+    fn with_raw_overflow_status_matches_unwrapped_guard() {
+        let client = Client::debug(rocket()).unwrap();
+        let body = "too long for the limit";
+        let wrapped = client.post("/string").body(body).dispatch();
+        let unwrapped = client.post("/plain").body(body).dispatch();
+        assert_eq!(wrapped.status(), unwrapped.status());
+    }
+}
@@ -0,0 +1,49 @@
+use rocket::http::{Accept, ContentType, Header, Status};
+use rocket::local::blocking::Client;
+
+mod default_catcher_negotiation_tests {
+    use super::*;
+
+    fn client() -> Client {
+        Client::debug_with(vec![]).unwrap()
+    }
+
+    #[test]
+    fn json_is_negotiated() {
+        let client = client();
+        let response = client.get("/missing").header(Accept::JSON).dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+        let body = response.into_string().unwrap();
+        assert!(body.contains(r#""code": 404"#), "{}", body);
+        assert!(body.contains(r#""reason": "Not Found""#), "{}", body);
+
+        let accept = Header::new("Accept", "text/html; q=0.5, application/json");
+        let response = client.get("/missing").header(accept).dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+    }
+
+    #[test]
+    fn plain_text_is_negotiated() {
+        let client = client();
+        let response = client.get("/missing").header(Accept::Plain).dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.content_type(), Some(ContentType::Plain));
+        assert_eq!(response.into_string().unwrap(),
+            "404: Not Found\nThe requested resource could not be found.\n");
+    }
+
+    #[test]
+    fn html_is_the_fallback() {
+        let client = client();
+        let response = client.get("/missing").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::HTML));
+
+        for accept in ["*/*", "text/*", "image/png", "text/html, application/json"] {
+            let response = client.get("/missing").header(Header::new("Accept", accept)).dispatch();
+            assert_eq!(response.status(), Status::NotFound);
+            assert_eq!(response.content_type(), Some(ContentType::HTML), "{}", accept);
+        }
+    }
+}
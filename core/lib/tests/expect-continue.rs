@@ -0,0 +1,78 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use rocket::data::{Data, ToByteUnit};
+
+#[post("/", data = "<body>")]
+fn string(body: String) -> String {
+    body
+}
+
+#[post("/raw", data = "<data>")]
+async fn raw(data: Data<'_>) -> String {
+    data.open(1.kibibytes()).into_string().await.unwrap().into_inner()
+}
+
+mod expect_continue_tests {
+    use super::*;
+
+    use rocket::Config;
+    use rocket::data::Limits;
+    use rocket::local::blocking::Client;
+    use rocket::http::{Header, Status};
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn limited_rocket() -> rocket::Rocket<rocket::Build> {
+        let limits = Limits::default().limit("string", 16.bytes());
+        let config = Config { limits, ..Config::debug_default() };
+        rocket::custom(config).mount("/", routes![string, raw])
+    }
+
+    #[test]
+    fn over_limit_expectation_fails() {
+        let client = Client::debug(limited_rocket()).unwrap();
+        let response = client.post("/")
+            .header(Header::new("Expect", "100-continue"))
+            .header(Header::new("Content-Length", "1000000"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::ExpectationFailed);
+    }
+
+    #[test]
+    fn within_limit_or_no_expectation_succeeds() {
+        let client = Client::debug(limited_rocket()).unwrap();
+        let response = client.post("/")
+            .header(Header::new("Expect", "100-continue"))
+            .body("hello")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "hello");
+
+        let response = client.post("/raw")
+            .header(Header::new("Expect", "100-Continue"))
+            .header(Header::new("Content-Length", "1000000"))
+            .body("hello")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "hello");
+    }
+
+    #[rocket::async_test]
+    async fn body_is_never_requested() {
+        let port = common::launch(limited_rocket()).await;
+
+        // Send only the head. If the server asked for the body with a `100
+        // Continue`, that would be the first thing we read back.
+        let mut stream = common::connect(port).await;
+        stream.write_all(b"POST / HTTP/1.1\r\n\
+            Host: localhost\r\n\
+            Content-Length: 1000000\r\n\
+            Expect: 100-continue\r\n\r\n").await.unwrap();
+
+        let mut buf = [0u8; 12];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"HTTP/1.1 417");
+    }
+}
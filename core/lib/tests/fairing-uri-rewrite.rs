@@ -0,0 +1,63 @@
+#[macro_use] extern crate rocket;
+
+use rocket::fairing::AdHoc;
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[get("/hello/<name>?<greeting>")]
+fn hello(name: &str, greeting: Option<&str>) -> String {
+    format!("{}, {}!", greeting.unwrap_or("Hello"), name)
+}
+
+mod fairing_uri_rewrite_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+    use rocket::http::Status;
+
+    fn prefix_stripping_client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![index, hello])
+            .attach(AdHoc::on_request("Strip /app", |req, _| Box::pin(async move {
+                let path = req.uri().path();
+                if path == "/app" || path.starts_with("/app/") {
+                    let stripped = req.uri().map_path(|p| match &p.as_str()["/app".len()..] {
+                        "" => "/",
+                        rest => rest,
+                    });
+
+                    if let Some(uri) = stripped {
+                        req.set_uri(uri);
+                    }
+                }
+            })));
+
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn routing_sees_rewritten_uri() {
+        let client = prefix_stripping_client();
+        let response = client.get("/app/hello/Bob?greeting=Hi").dispatch();
+        assert_eq!(response.into_string().unwrap(), "Hi, Bob!");
+
+        let response = client.get("/app/hello/Alice").dispatch();
+        assert_eq!(response.into_string().unwrap(), "Hello, Alice!");
+
+        let response = client.get("/app").dispatch();
+        assert_eq!(response.into_string().unwrap(), "index");
+    }
+
+    #[test]
+    fn unprefixed_uris_are_untouched() {
+        let client = prefix_stripping_client();
+        let response = client.get("/hello/Bob").dispatch();
+        assert_eq!(response.into_string().unwrap(), "Hello, Bob!");
+
+        let response = client.get("/application/hello/Bob").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
@@ -20,6 +20,8 @@ fn rocket() -> Rocket<Build> {
         .mount("/both", FileServer::new(&root, Options::DotFiles | Options::Index))
         .mount("/redir", FileServer::new(&root, Options::NormalizeDirs))
         .mount("/redir_index", FileServer::new(&root, Options::NormalizeDirs | Options::Index))
+        .mount("/listing", FileServer::new(&root, Options::Listing))
+        .mount("/index_listing", FileServer::new(&root, Options::Index | Options::Listing))
 }
 
 static REGULAR_FILES: &[&str] = &[
@@ -186,3 +188,169 @@ fn test_redirection() {
     assert_eq!(response.status(), Status::PermanentRedirect);
     assert_eq!(response.headers().get("Location").next(), Some("/redir_index/"));
 }
+
+#[test]
+fn test_listing() {
+    let client = Client::debug(rocket()).expect("valid rocket");
+    assert_all(&client, "listing", REGULAR_FILES, true);
+    assert_all(&client, "listing", HIDDEN_FILES, false);
+
+    let response = client.get("/listing/inner").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let html = response.into_string().unwrap();
+    assert!(html.contains(r#"<a href="/listing/inner/goodbye">goodbye</a>"#));
+    assert!(html.contains(r#"<a href="/listing/inner/index.html">index.html</a>"#));
+    assert!(!html.contains(".hideme"));
+
+    let response = client.get("/listing/").dispatch();
+    let html = response.into_string().unwrap();
+    assert!(html.contains(r#"<a href="/listing/inner/">inner/</a>"#));
+    assert!(html.contains(r#"<a href="/listing/other/">other/</a>"#));
+    assert!(!html.contains(".hidden"));
+
+    // Index files take precedence over listings.
+    assert_all(&client, "index_listing", INDEXED_DIRECTORIES, true);
+    let response = client.get("/index_listing/other").dispatch();
+    let html = response.into_string().unwrap();
+    assert!(html.contains(r#"<a href="/index_listing/other/hello.txt">hello.txt</a>"#));
+}
+
+#[test]
+fn test_open_dir() {
+    use std::io::ErrorKind;
+    use rocket::fs::{NamedFile, DirResponse};
+
+    let root = static_root();
+    let open_dir = |path: &str, options| {
+        rocket::async_test(NamedFile::open_dir(root, path, options))
+    };
+
+    match open_dir("inner", Options::Index) {
+        Ok(DirResponse::File(file)) => assert_eq!(file.path(), root.join("inner/index.html")),
+        other => panic!("expected index file, got {:?}", other),
+    }
+
+    match open_dir("other", Options::Index | Options::Listing) {
+        Ok(DirResponse::Listing(listing)) => assert_eq!(listing.path(), root.join("other")),
+        other => panic!("expected listing, got {:?}", other),
+    }
+
+    match open_dir("./other/hello.txt", Options::None) {
+        Ok(DirResponse::File(file)) => assert_eq!(file.path(), root.join("other/hello.txt")),
+        other => panic!("expected file, got {:?}", other),
+    }
+
+    assert_eq!(open_dir("other", Options::Index).unwrap_err().kind(), ErrorKind::NotFound);
+    assert_eq!(open_dir(".hidden", Options::None).unwrap_err().kind(), ErrorKind::NotFound);
+    assert!(open_dir(".hidden", Options::DotFiles).is_ok());
+
+    for escape in ["..", "../static/index.html", "inner/../../static", "/etc/passwd"] {
+        let error = open_dir(escape, Options::Index | Options::Listing).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::PermissionDenied, "{}", escape);
+    }
+}
+
+#[test]
+fn test_listing_escapes_names() {
+    use rocket::fs::NamedFile;
+
+    let root = std::env::temp_dir().join(format!("rocket-listing-{}", std::process::id()));
+    std::fs::create_dir_all(&root).expect("create temp dir");
+    std::fs::write(root.join("<b>&\"quoted\" file.txt"), "").expect("create file");
+
+    #[rocket::get("/<path..>")]
+    async fn files(path: std::path::PathBuf) -> Option<rocket::fs::DirResponse> {
+        let root = std::env::temp_dir().join(format!("rocket-listing-{}", std::process::id()));
+        NamedFile::open_dir(root, path, Options::Listing).await.ok()
+    }
+
+    let client = Client::debug_with(rocket::routes![files]).expect("valid rocket");
+    let html = client.get("/").dispatch().into_string().unwrap();
+    std::fs::remove_dir_all(&root).expect("remove temp dir");
+
+    assert!(!html.contains("<b>"));
+    assert!(html.contains("&lt;b&gt;&amp;&quot;quoted&quot; file.txt</a>"));
+    assert!(html.contains(r#"href="/%3Cb%3E%26%22quoted%22%20file.txt""#));
+}
+
+#[test]
+fn test_precompressed() {
+    use rocket::http::{ContentType, Header};
+
+    let parent = std::env::temp_dir().join(format!("rocket-precompressed-{}", std::process::id()));
+    let root = parent.join("public");
+    std::fs::create_dir_all(root.join("dir")).expect("create temp dir");
+    std::fs::write(parent.join("secret.txt"), "secret").expect("create file");
+    std::fs::write(parent.join("secret.txt.gz"), "secret").expect("create file");
+    for (file, contents) in &[
+        ("app.js", "identity"), ("app.js.br", "brotli"), ("app.js.gz", "gzip"),
+        ("style.css", "identity"), ("style.css.gz", "gzip"),
+        ("dir/index.html", "identity"), ("dir/index.html.gz", "gzip"),
+        ("orphan.txt.gz", "gzip"),
+    ] {
+        std::fs::write(root.join(file), contents).expect("create file");
+    }
+
+    let rocket = rocket::build()
+        .mount("/gz", FileServer::new(&root, Options::Index | Options::Precompressed))
+        .mount("/plain", FileServer::from(&root));
+
+    let client = Client::debug(rocket).expect("valid rocket");
+    let get = |path: &str, accept: Option<&str>| {
+        let mut request = client.get(path.to_string());
+        if let Some(accept) = accept {
+            request.add_header(Header::new("Accept-Encoding", accept.to_string()));
+        }
+
+        let response = request.dispatch();
+        let status = response.status();
+        let encoding = response.headers().get_one("Content-Encoding").map(String::from);
+        let vary = response.headers().get_one("Vary").map(String::from);
+        let content_type = response.content_type();
+        (status, encoding, vary, content_type, response.into_string())
+    };
+
+    let (status, encoding, vary, ct, body) = get("/gz/app.js", Some("gzip, deflate, br"));
+    assert_eq!(status, Status::Ok);
+    assert_eq!(encoding.as_deref(), Some("br"));
+    assert_eq!(vary.as_deref(), Some("Accept-Encoding"));
+    assert_eq!(ct, Some(ContentType::JavaScript));
+    assert_eq!(body.as_deref(), Some("brotli"));
+
+    let (_, encoding, _, _, body) = get("/gz/app.js", Some("br;q=0.5, gzip"));
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+    assert_eq!(body.as_deref(), Some("gzip"));
+
+    let (_, encoding, _, ct, body) = get("/gz/style.css", Some("br, gzip"));
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+    assert_eq!(ct, Some(ContentType::CSS));
+    assert_eq!(body.as_deref(), Some("gzip"));
+
+    let (_, encoding, _, ct, body) = get("/gz/dir/", Some("*"));
+    assert_eq!(encoding.as_deref(), Some("gzip"));
+    assert_eq!(ct, Some(ContentType::HTML));
+    assert_eq!(body.as_deref(), Some("gzip"));
+
+    for accept in &[None, Some("identity"), Some("br;q=0, gzip;q=0"), Some("*;q=0")] {
+        let (status, encoding, vary, ct, body) = get("/gz/app.js", *accept);
+        assert_eq!(status, Status::Ok);
+        assert_eq!(encoding, None);
+        assert_eq!(vary.as_deref(), Some("Accept-Encoding"));
+        assert_eq!(ct, Some(ContentType::JavaScript));
+        assert_eq!(body.as_deref(), Some("identity"));
+    }
+
+    let (status, ..) = get("/gz/orphan.txt", Some("gzip"));
+    assert_eq!(status, Status::NotFound);
+
+    for path in &["/gz/../secret.txt", "/gz/dir/../../secret.txt", "/gz/%2E%2E/secret.txt"] {
+        let (status, ..) = get(path, Some("gzip"));
+        assert_eq!(status, Status::NotFound, "{} escaped the root", path);
+    }
+
+    let (_, encoding, vary, _, body) = get("/plain/app.js", Some("br, gzip"));
+    assert_eq!((encoding, vary), (None, None));
+    assert_eq!(body.as_deref(), Some("identity"));
+
+    std::fs::remove_dir_all(&parent).expect("remove temp dir");
+}
@@ -0,0 +1,60 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use std::io::Cursor;
+
+use rocket::{Request, Response};
+use rocket::response::{self, Responder};
+
+const BODY: &str = "Hello, chunked world!";
+
+struct Sized(bool);
+
+impl<'r> Responder<'r, 'static> for Sized {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = Response::build();
+        response.sized_body(BODY.len(), Cursor::new(BODY));
+        if self.0 {
+            response.force_chunked();
+        }
+
+        response.ok()
+    }
+}
+
+#[get("/chunked")]
+fn chunked() -> Sized {
+    Sized(true)
+}
+
+#[get("/sized")]
+fn sized() -> Sized {
+    Sized(false)
+}
+
+mod force_chunked_tests {
+    use super::*;
+
+    async fn get(path: &str) -> String {
+        let rocket = rocket::build().mount("/", routes![chunked, sized]);
+        let port = common::launch(rocket).await;
+        common::get(port, path).await.to_ascii_lowercase()
+    }
+
+    #[rocket::async_test]
+    async fn force_chunked_omits_content_length() {
+        let response = get("/chunked").await;
+        assert!(response.contains("transfer-encoding: chunked\r\n"), "{}", response);
+        assert!(!response.contains("content-length"), "{}", response);
+        assert!(response.contains(&BODY.to_ascii_lowercase()));
+    }
+
+    #[rocket::async_test]
+    async fn sized_body_has_content_length() {
+        let response = get("/sized").await;
+        let content_length = format!("content-length: {}\r\n", BODY.len());
+        assert!(response.contains(&content_length), "{}", response);
+        assert!(!response.contains("transfer-encoding"), "{}", response);
+    }
+}
@@ -0,0 +1,63 @@
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+struct Admin;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Admin {
+    type Error = ();
+
+    async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        request::Outcome::Forward(())
+    }
+}
+
+#[get("/item/<id>")]
+fn by_id(id: usize) -> String { id.to_string() }
+
+#[get("/item/<_name>", rank = 2)]
+fn admin(_name: &str, _admin: Admin) -> &'static str { "admin" }
+
+#[post("/item/<_name>")]
+fn create(_name: &str) -> &'static str { "created" }
+
+#[catch(404)]
+fn trace(req: &Request) -> String {
+    req.forwards().iter()
+        .map(|f| format!("{} {}: {}", f.route().method, f.route().uri, f.reason()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+mod forward_trace_tests {
+    use super::*;
+
+    use rocket::Config;
+    use rocket::local::blocking::Client;
+
+    fn rocket(trace_forwards: bool) -> rocket::Rocket<rocket::Build> {
+        let config = Config { trace_forwards, ..Config::debug_default() };
+        rocket::custom(config)
+            .mount("/", routes![by_id, admin, create])
+            .register("/", catchers![trace])
+    }
+
+    #[test]
+    fn trace_lists_tried_routes_and_reasons() {
+        let client = Client::debug(rocket(true)).unwrap();
+        let response = client.get("/item/bob").dispatch();
+        assert_eq!(response.into_string().unwrap(),
+            "GET /item/<id>: parameter guard `id: usize`: \"bob\"\n\
+            GET /item/<_name>: request guard `Admin`\n\
+            POST /item/<_name>: method mismatch");
+    }
+
+    #[test]
+    fn trace_is_empty_when_disabled() {
+        let client = Client::debug(rocket(false)).unwrap();
+        let response = client.get("/item/bob").dispatch();
+        assert_eq!(response.into_string().unwrap(), "");
+    }
+}
@@ -1,5 +1,7 @@
 #[macro_use] extern crate rocket;
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use rocket::http::Status;
 use rocket::response::content::RawJson;
 
@@ -18,6 +20,14 @@ fn other() -> RawJson<&'static str> {
     RawJson("{ 'hi': 'hello' }")
 }
 
+static EXPENSIVE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[get("/expensive", auto_head = false)]
+fn expensive() -> &'static str {
+    EXPENSIVE_CALLS.fetch_add(1, Ordering::SeqCst);
+    "expensive"
+}
+
 mod head_handling_tests {
     use super::*;
 
@@ -26,7 +36,7 @@ mod head_handling_tests {
     use rocket::http::{Status, ContentType};
 
     fn routes() -> Vec<Route> {
-        routes![index, empty, other]
+        routes![index, empty, other, expensive]
     }
 
     #[test]
@@ -56,4 +66,16 @@ mod head_handling_tests {
         assert_eq!(response.body().preset_size(), Some(17));
         assert!(response.into_bytes().unwrap().is_empty());
     }
+
+    #[test]
+    fn opted_out_of_auto_head() {
+        let client = Client::debug_with(routes()).unwrap();
+        let response = client.head("/expensive").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(EXPENSIVE_CALLS.load(Ordering::SeqCst), 0);
+
+        let response = client.get("/expensive").dispatch();
+        assert_eq!(response.into_string().unwrap(), "expensive");
+        assert_eq!(EXPENSIVE_CALLS.load(Ordering::SeqCst), 1);
+    }
 }
@@ -0,0 +1,99 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+mod header_limits_tests {
+    use super::*;
+
+    use rocket::Config;
+    use rocket::data::ToByteUnit;
+    use rocket::local::blocking::Client;
+    use rocket::http::{Header, Status};
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn limited_rocket() -> rocket::Rocket<rocket::Build> {
+        let config = Config {
+            max_headers: 4,
+            max_header_size: 1.kibibytes(),
+            ..Config::debug_default()
+        };
+
+        rocket::custom(config).mount("/", routes![index])
+    }
+
+    #[test]
+    fn within_limits() {
+        let client = Client::debug(limited_rocket()).unwrap();
+        let response = client.get("/")
+            .header(Header::new("X-A", "a".repeat(512)))
+            .header(Header::new("X-B", "b"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "index");
+    }
+
+    #[test]
+    fn too_many_headers() {
+        let client = Client::debug(limited_rocket()).unwrap();
+        let mut request = client.get("/");
+        for i in 0..5 {
+            request.add_header(Header::new(format!("X-{}", i), "value"));
+        }
+
+        assert_eq!(request.dispatch().status(), Status::RequestHeaderFieldsTooLarge);
+    }
+
+    #[test]
+    fn max_headers_is_capped_at_server_limit() {
+        let config = Config { max_headers: 500, ..Config::debug_default() };
+        let client = Client::debug(rocket::custom(config)).unwrap();
+        assert_eq!(client.rocket().config().max_headers, 100);
+
+        let mut request = client.get("/");
+        for i in 0..101 {
+            request.add_header(Header::new(format!("X-{}", i), "value"));
+        }
+
+        assert_eq!(request.dispatch().status(), Status::RequestHeaderFieldsTooLarge);
+    }
+
+    #[test]
+    fn oversized_header() {
+        let client = Client::debug(limited_rocket()).unwrap();
+        let response = client.get("/")
+            .header(Header::new("X-Big", "a".repeat(2048)))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::RequestHeaderFieldsTooLarge);
+    }
+
+    async fn wire_status(header_size: usize) -> String {
+        let port = common::launch(limited_rocket()).await;
+        let mut stream = common::connect(port).await;
+        let head = format!("GET / HTTP/1.1\r\nHost: localhost\r\nX-Big: {}\r\n\r\n",
+            "a".repeat(header_size));
+
+        // The server may stop reading and respond before we're done writing.
+        let _ = stream.write_all(head.as_bytes()).await;
+        let mut buf = [0u8; 12];
+        stream.read_exact(&mut buf).await.unwrap();
+        String::from_utf8_lossy(&buf).into_owned()
+    }
+
+    #[rocket::async_test]
+    async fn oversized_header_on_the_wire() {
+        // Larger than the configured size but fits in the server's buffer.
+        assert_eq!(wire_status(2048).await, "HTTP/1.1 431");
+
+        // Doesn't fit in the server's buffer at all.
+        assert_eq!(wire_status(32 * 1024).await, "HTTP/1.1 431");
+
+        // Small enough.
+        assert_eq!(wire_status(512).await, "HTTP/1.1 200");
+    }
+}
@@ -0,0 +1,102 @@
+#[macro_use] extern crate rocket;
+
+use rocket::form::Form;
+
+#[derive(FromForm)]
+struct FormData {
+    form_data: String,
+}
+
+#[put("/")]
+fn put() -> &'static str {
+    "PUT"
+}
+
+#[post("/")]
+fn post() -> &'static str {
+    "POST"
+}
+
+#[patch("/", data = "<form_data>")]
+fn patch(form_data: Form<FormData>) -> String {
+    format!("PATCH: {}", form_data.form_data)
+}
+
+mod header_method_override_tests {
+    use super::*;
+
+    use rocket::Config;
+    use rocket::local::blocking::Client;
+    use rocket::http::{Header, ContentType, Status};
+
+    fn override_client(enabled: bool) -> Client {
+        let config = Config { header_method_override: enabled, ..Config::debug_default() };
+        let rocket = rocket::custom(config).mount("/", routes![put, post, patch]);
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn header_override_when_enabled() {
+        let client = override_client(true);
+        let response = client.post("/")
+            .header(Header::new("X-HTTP-Method-Override", "PUT"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "PUT");
+
+        let response = client.post("/")
+            .header(Header::new("X-HTTP-Method-Override", "put"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "PUT");
+    }
+
+    #[test]
+    fn header_ignored_by_default() {
+        let client = Client::debug_with(routes![put, post, patch]).unwrap();
+        let response = client.post("/")
+            .header(Header::new("X-HTTP-Method-Override", "PUT"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "POST");
+
+        let client = override_client(false);
+        let response = client.post("/")
+            .header(Header::new("X-HTTP-Method-Override", "PUT"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "POST");
+    }
+
+    #[test]
+    fn header_only_applies_to_post() {
+        let client = override_client(true);
+        let response = client.get("/")
+            .header(Header::new("X-HTTP-Method-Override", "PUT"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+    }
+
+    #[test]
+    fn invalid_header_is_ignored() {
+        let client = override_client(true);
+        let response = client.post("/")
+            .header(Header::new("X-HTTP-Method-Override", "FROB"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "POST");
+    }
+
+    #[test]
+    fn form_field_takes_precedence() {
+        let client = override_client(true);
+        let response = client.post("/")
+            .header(ContentType::Form)
+            .header(Header::new("X-HTTP-Method-Override", "PUT"))
+            .body("_method=patch&form_data=Form+data")
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "PATCH: Form data");
+    }
+}
@@ -0,0 +1,80 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use std::time::Duration;
+
+use rocket::tokio::time::sleep;
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[get("/slow")]
+async fn slow() -> &'static str {
+    sleep(Duration::from_secs(2)).await;
+    "slow"
+}
+
+mod idle_timeout_tests {
+    use super::*;
+
+    use rocket::Config;
+    use rocket::tokio::net::TcpStream;
+    use rocket::tokio::time::timeout;
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn launch(idle_timeout: u32) -> u16 {
+        let config = Config { idle_timeout, ..Config::debug_default() };
+        common::launch(rocket::custom(config).mount("/", routes![index, slow])).await
+    }
+
+    async fn read_response(stream: &mut TcpStream, body: &str) {
+        let mut buf = vec![0; 1024];
+        let mut response = String::new();
+        while !response.ends_with(body) {
+            let n = stream.read(&mut buf).await.unwrap();
+            assert_ne!(n, 0, "connection closed early: {:?}", response);
+            response.push_str(std::str::from_utf8(&buf[..n]).unwrap());
+        }
+
+        assert!(response.starts_with("HTTP/1.1 200"));
+    }
+
+    #[rocket::async_test]
+    async fn idle_connection_is_closed() {
+        let port = launch(1).await;
+
+        // A connection that never sends anything.
+        let mut silent = common::connect(port).await;
+        let read = timeout(Duration::from_secs(5), silent.read(&mut [0; 16])).await;
+        assert_eq!(read.expect("connection not closed").unwrap_or(0), 0);
+
+        // A kept-alive connection that goes idle after a request.
+        let mut stream = common::connect(port).await;
+        stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        read_response(&mut stream, "index").await;
+
+        let read = timeout(Duration::from_secs(5), stream.read(&mut [0; 16])).await;
+        assert_eq!(read.expect("connection not closed").unwrap_or(0), 0);
+    }
+
+    #[rocket::async_test]
+    async fn in_flight_request_is_not_idle() {
+        let port = launch(1).await;
+
+        let mut stream = common::connect(port).await;
+        stream.write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        read_response(&mut stream, "slow").await;
+    }
+
+    #[rocket::async_test]
+    async fn zero_disables_idle_timeout() {
+        let port = launch(0).await;
+
+        let mut stream = common::connect(port).await;
+        let read = timeout(Duration::from_secs(2), stream.read(&mut [0; 16])).await;
+        assert!(read.is_err(), "connection unexpectedly closed");
+    }
+}
@@ -0,0 +1,104 @@
+#[macro_use] extern crate rocket;
+
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use rocket::Config;
+use rocket::listener::{Listener, Connection};
+use rocket::tokio::io::{AsyncRead, AsyncWrite, DuplexStream, ReadBuf};
+use rocket::tokio::sync::mpsc;
+
+struct Pipe(DuplexStream);
+
+struct PipeListener(mpsc::UnboundedReceiver<DuplexStream>);
+
+impl Listener for PipeListener {
+    type Connection = Pipe;
+
+    fn local_addr(&self) -> Option<SocketAddr> {
+        Some(([10, 0, 0, 1], 4321).into())
+    }
+
+    fn poll_accept(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<Pipe>> {
+        match self.0.poll_recv(cx) {
+            Poll::Ready(Some(stream)) => Poll::Ready(Ok(Pipe(stream))),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+impl Connection for Pipe {
+    fn peer_address(&self) -> Option<SocketAddr> {
+        Some(([10, 0, 0, 2], 1234).into())
+    }
+
+    fn enable_nodelay(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncRead for Pipe {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Pipe {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8]
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+#[get("/")]
+fn index(config: &Config, remote: SocketAddr) -> String {
+    format!("{}:{} from {}", config.address, config.port, remote)
+}
+
+mod launch_on_tests {
+    use super::*;
+
+    use rocket::tokio::io::{self as tio, AsyncReadExt, AsyncWriteExt};
+
+    async fn get(connect: &mpsc::UnboundedSender<DuplexStream>) -> String {
+        let (mut client, server) = tio::duplex(4096);
+        connect.send(server).unwrap();
+        client.write_all(b"GET / HTTP/1.1\r\nHost: pipe\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[rocket::async_test]
+    async fn serves_requests_from_custom_listener() {
+        let (connect, accept) = mpsc::unbounded_channel();
+        let rocket = rocket::build().mount("/", routes![index]);
+        rocket::tokio::spawn(rocket.launch_on(PipeListener(accept)));
+
+        for _ in 0..2 {
+            let response = get(&connect).await;
+            assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+            assert!(response.ends_with("\r\n\r\n10.0.0.1:4321 from 10.0.0.2:1234"), "{}", response);
+        }
+    }
+}
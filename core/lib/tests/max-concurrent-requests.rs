@@ -0,0 +1,120 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use rocket::State;
+use rocket::tokio::sync::Notify;
+
+#[get("/wait")]
+async fn wait(notify: &State<Notify>) -> &'static str {
+    notify.notified().await;
+    "done"
+}
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+mod max_concurrent_requests_tests {
+    use super::*;
+
+    use rocket::Config;
+    use rocket::local::asynchronous::Client;
+    use rocket::http::Status;
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn rocket(max_concurrent_requests: usize) -> rocket::Rocket<rocket::Build> {
+        let config = Config { max_concurrent_requests, ..Config::debug_default() };
+        rocket_with(config)
+    }
+
+    fn rocket_with(config: Config) -> rocket::Rocket<rocket::Build> {
+        rocket::custom(config)
+            .manage(Notify::new())
+            .mount("/", routes![wait, index])
+    }
+
+    #[rocket::async_test]
+    async fn requests_beyond_limit_are_shed() {
+        let client = Client::debug(rocket(1)).await.unwrap();
+        let notify = client.rocket().state::<Notify>().unwrap();
+
+        let waiting = client.get("/wait").dispatch();
+        let shed = async {
+            let response = client.get("/").dispatch().await;
+            notify.notify_one();
+            response
+        };
+
+        let (waiting, shed) = rocket::tokio::join!(waiting, shed);
+        assert_eq!(waiting.status(), Status::Ok);
+        assert!(waiting.headers().get_one("Connection").is_none());
+
+        assert_eq!(shed.status(), Status::ServiceUnavailable);
+        assert_eq!(shed.headers().get_one("Connection"), Some("close"));
+        assert_eq!(shed.headers().get_one("Retry-After"), Some("5"));
+
+        let response = client.get("/").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.headers().get_one("Connection").is_none());
+        assert!(response.headers().get_one("Retry-After").is_none());
+    }
+
+    #[rocket::async_test]
+    async fn retry_after_is_configurable() {
+        for (retry_after, expected) in [(30, Some("30")), (0, None)] {
+            let config = Config { max_concurrent_requests: 1, retry_after, ..Config::debug_default() };
+            let client = Client::debug(rocket_with(config)).await.unwrap();
+            let notify = client.rocket().state::<Notify>().unwrap();
+
+            let waiting = client.get("/wait").dispatch();
+            let shed = async {
+                let response = client.get("/").dispatch().await;
+                notify.notify_one();
+                response
+            };
+
+            let (_, shed) = rocket::tokio::join!(waiting, shed);
+            assert_eq!(shed.status(), Status::ServiceUnavailable);
+            assert_eq!(shed.headers().get_one("Retry-After"), expected);
+        }
+    }
+
+    #[rocket::async_test]
+    async fn zero_is_unlimited() {
+        let client = Client::debug(rocket(0)).await.unwrap();
+        let notify = client.rocket().state::<Notify>().unwrap();
+
+        let waiting = client.get("/wait").dispatch();
+        let other = async {
+            let response = client.get("/").dispatch().await;
+            notify.notify_one();
+            response
+        };
+
+        let (waiting, other) = rocket::tokio::join!(waiting, other);
+        assert_eq!(waiting.status(), Status::Ok);
+        assert_eq!(other.status(), Status::Ok);
+    }
+
+    #[rocket::async_test]
+    async fn shed_connection_is_closed() {
+        let port = common::launch(rocket(1)).await;
+
+        // Occupy the only slot.
+        let mut waiting = common::connect(port).await;
+        waiting.write_all(b"GET /wait HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        rocket::tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+
+        // The server should respond with a 503 and then close the connection,
+        // so reading to the end must terminate.
+        let mut shed = common::connect(port).await;
+        shed.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        shed.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 503"));
+        assert!(response.to_ascii_lowercase().contains("connection: close"));
+        assert!(response.to_ascii_lowercase().contains("retry-after: 5"));
+    }
+}
@@ -0,0 +1,99 @@
+#[macro_use] extern crate rocket;
+
+use std::sync::Arc;
+
+use rocket::State;
+use rocket::data::{Data, ToByteUnit};
+use rocket::tokio::io::AsyncReadExt;
+use rocket::tokio::sync::Notify;
+
+#[derive(Default)]
+struct Signals {
+    held: Notify,
+    release: Notify,
+}
+
+#[post("/hold", data = "<data>")]
+async fn hold(data: Data<'_>, signals: &State<Arc<Signals>>) -> &'static str {
+    let mut stream = data.open(1.kibibytes());
+    stream.read_exact(&mut [0u8; 1]).await.unwrap();
+    signals.held.notify_one();
+    signals.release.notified().await;
+    "held"
+}
+
+#[post("/read", data = "<data>")]
+async fn read(data: Data<'_>) -> String {
+    let bytes = data.open(1.kibibytes()).into_bytes().await.unwrap();
+    bytes.len().to_string()
+}
+
+mod max_total_body_bytes_tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use rocket::Config;
+    use rocket::local::asynchronous::Client;
+    use rocket::tokio::{self, time::timeout};
+
+    async fn client(max_total_body_bytes: u64) -> (Client, Arc<Signals>) {
+        let signals = Arc::new(Signals::default());
+        let config = Config {
+            max_total_body_bytes: max_total_body_bytes.bytes(),
+            ..Config::debug_default()
+        };
+
+        let rocket = rocket::custom(config)
+            .manage(signals.clone())
+            .mount("/", routes![hold, read]);
+
+        (Client::untracked(rocket).await.unwrap(), signals)
+    }
+
+    #[rocket::async_test]
+    async fn reads_wait_for_budget() {
+        let (client, signals) = client(1024).await;
+        let client = Arc::new(client);
+        let body = vec![b'a'; 1024];
+
+        let holder = tokio::spawn({
+            let (client, body) = (client.clone(), body.clone());
+            async move {
+                client.post("/hold").body(body).dispatch().await.into_string().await
+            }
+        });
+
+        signals.held.notified().await;
+        let mut reader = Box::pin(client.post("/read").body(&body).dispatch());
+        let blocked = timeout(Duration::from_millis(250), reader.as_mut()).await;
+        assert!(blocked.is_err(), "read proceeded past an exhausted budget");
+
+        signals.release.notify_one();
+        let response = timeout(Duration::from_secs(5), reader).await.unwrap();
+        assert_eq!(response.into_string().await.unwrap(), "1024");
+        assert_eq!(holder.await.unwrap().unwrap(), "held");
+    }
+
+    #[rocket::async_test]
+    async fn unlimited_budget_does_not_wait() {
+        let (client, signals) = client(0).await;
+        let client = Arc::new(client);
+        let body = vec![b'a'; 1024];
+
+        let holder = tokio::spawn({
+            let (client, body) = (client.clone(), body.clone());
+            async move {
+                client.post("/hold").body(body).dispatch().await.into_string().await
+            }
+        });
+
+        signals.held.notified().await;
+        let read = client.post("/read").body(&body).dispatch();
+        let response = timeout(Duration::from_secs(5), read).await.unwrap();
+        assert_eq!(response.into_string().await.unwrap(), "1024");
+
+        signals.release.notify_one();
+        assert_eq!(holder.await.unwrap().unwrap(), "held");
+    }
+}
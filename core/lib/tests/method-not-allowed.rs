@@ -0,0 +1,64 @@
+#[macro_use] extern crate rocket;
+
+#[get("/resource")]
+fn get() -> &'static str {
+    "resource"
+}
+
+#[put("/resource")]
+fn put() -> &'static str {
+    "updated"
+}
+
+#[get("/read-only")]
+fn read_only() -> &'static str {
+    "read only"
+}
+
+#[post("/no-head", data = "<_body>")]
+fn post(_body: &str) { }
+
+#[get("/forwards")]
+fn forwards() -> Option<&'static str> {
+    None
+}
+
+mod method_not_allowed_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        let routes = routes![get, put, read_only, post, forwards];
+        Client::debug_with(routes).unwrap()
+    }
+
+    #[test]
+    fn wrong_method_is_405_with_allow() {
+        let client = client();
+        let response = client.post("/read-only").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD"));
+
+        let response = client.delete("/resource").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("GET, PUT, HEAD"));
+
+        let response = client.head("/no-head").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("POST"));
+    }
+
+    #[test]
+    fn unmatched_path_is_still_404() {
+        let client = client();
+        let response = client.post("/nothing-here").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert!(response.headers().get_one("Allow").is_none());
+
+        let response = client.get("/forwards").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert!(response.headers().get_one("Allow").is_none());
+    }
+}
@@ -0,0 +1,31 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use rocket::http::{Cookie, CookieJar};
+
+#[get("/")]
+fn cookies(jar: &CookieJar<'_>) -> &'static str {
+    jar.add(Cookie::new("a", "1"));
+    jar.add(Cookie::new("b", "2"));
+    jar.add(Cookie::new("c", "3"));
+    "cookies"
+}
+
+mod multiple_set_cookie_headers_tests {
+    use super::*;
+
+    #[rocket::async_test]
+    async fn each_cookie_is_a_separate_header() {
+        let port = common::launch(rocket::build().mount("/", routes![cookies])).await;
+        let response = common::get(port, "/").await;
+        let mut set_cookies: Vec<_> = response.lines()
+            .filter_map(|line| line.split_once(": "))
+            .filter(|(name, _)| name.eq_ignore_ascii_case("set-cookie"))
+            .filter_map(|(_, value)| value.split(';').next())
+            .collect();
+
+        set_cookies.sort();
+        assert_eq!(set_cookies, &["a=1", "b=2", "c=3"]);
+    }
+}
@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::PathBuf;
+
+use rocket::Config;
+use rocket::fs::{ContentTypes, FileServer};
+use rocket::http::ContentType;
+use rocket::local::blocking::Client;
+
+fn root() -> PathBuf {
+    let root = std::env::temp_dir()
+        .join(format!("rocket-named-file-content-types-{}", std::process::id()));
+
+    fs::create_dir_all(&root).unwrap();
+    for name in &["site.webmanifest", "app.js", "index.html", "data.xyz", "LICENSE"] {
+        fs::write(root.join(name), name).unwrap();
+    }
+
+    root
+}
+
+#[test]
+fn registered_content_types_are_used() {
+    let manifest = ContentType::new("application", "manifest+json");
+    let content_types = ContentTypes::new()
+        .content_type("webmanifest", manifest.clone())
+        .content_type("JS", ContentType::Plain);
+
+    let root = root();
+    let config = Config { content_types, ..Config::debug_default() };
+    let rocket = rocket::custom(config).mount("/", FileServer::from(&root));
+    let client = Client::debug(rocket).unwrap();
+
+    let expected = [
+        ("/site.webmanifest", manifest),
+        ("/app.js", ContentType::Plain),
+        ("/index.html", ContentType::HTML),
+        ("/data.xyz", ContentType::Binary),
+        ("/LICENSE", ContentType::Binary),
+    ];
+
+    for (path, content_type) in expected.iter() {
+        let response = client.get(*path).dispatch();
+        assert_eq!(response.content_type().as_ref(), Some(content_type), "{}", path);
+    }
+
+    fs::remove_dir_all(&root).unwrap();
+}
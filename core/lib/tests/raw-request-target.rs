@@ -0,0 +1,53 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+struct Raw(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Raw {
+    type Error = std::convert::Infallible;
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let raw = format!("{:?} {:?}", req.http_version(), req.raw_target());
+        request::Outcome::Success(Raw(raw))
+    }
+}
+
+#[get("/<_..>")]
+fn raw(raw: Raw) -> String {
+    raw.0
+}
+
+mod raw_request_target_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+
+    async fn send(port: u16, request: &str) -> String {
+        let response = common::send(port, request).await;
+        response.split("\r\n\r\n").nth(1).unwrap().to_string()
+    }
+
+    #[rocket::async_test]
+    async fn version_and_target_are_retained() {
+        let port = common::launch(rocket::build().mount("/", routes![raw])).await;
+
+        let body = send(port, "GET /a/b?c=d HTTP/1.1\r\nHost: localhost\r\n\
+            Connection: close\r\n\r\n").await;
+        assert_eq!(body, r#"Some(HTTP/1.1) Some("/a/b?c=d")"#);
+
+        let body = send(port, "GET http://localhost/a?c HTTP/1.0\r\n\r\n").await;
+        assert_eq!(body, r#"Some(HTTP/1.0) Some("http://localhost/a?c")"#);
+    }
+
+    #[test]
+    fn local_requests_have_no_raw_metadata() {
+        let client = Client::debug_with(routes![raw]).unwrap();
+        let response = client.get("/a/b?c=d").dispatch();
+        assert_eq!(response.into_string().unwrap(), "None None");
+    }
+}
@@ -0,0 +1,76 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use rocket::tokio::io;
+use rocket::response::upgrade::Upgrade;
+
+#[get("/echo")]
+fn echo() -> Upgrade {
+    Upgrade::new("echo", |stream| async move {
+        let (mut reader, mut writer) = io::split(stream);
+        io::copy(&mut reader, &mut writer).await?;
+        Ok(())
+    })
+}
+
+mod raw_upgrade_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+    use rocket::http::{Header, Status};
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn upgrade_required_without_upgrade_header() {
+        let client = Client::debug_with(routes![echo]).unwrap();
+        let response = client.get("/echo").dispatch();
+        assert_eq!(response.status(), Status::UpgradeRequired);
+        assert_eq!(response.headers().get_one("Upgrade"), Some("echo"));
+
+        let response = client.get("/echo")
+            .header(Header::new("Upgrade", "h2c, websocket"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::UpgradeRequired);
+    }
+
+    #[test]
+    fn switching_protocols_response() {
+        let client = Client::debug_with(routes![echo]).unwrap();
+        let response = client.get("/echo")
+            .header(Header::new("Connection", "Upgrade"))
+            .header(Header::new("Upgrade", "foo, ECHO"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::SwitchingProtocols);
+        assert_eq!(response.headers().get_one("Connection"), Some("Upgrade"));
+        assert_eq!(response.headers().get_one("Upgrade"), Some("echo"));
+    }
+
+    #[rocket::async_test]
+    async fn echo_over_raw_upgrade() {
+        let port = common::launch(rocket::build().mount("/", routes![echo])).await;
+        let mut stream = common::connect(port).await;
+        stream.write_all(b"GET /echo HTTP/1.1\r\n\
+            Host: localhost\r\n\
+            Connection: Upgrade\r\n\
+            Upgrade: echo\r\n\r\n").await.unwrap();
+
+        // Read the response head, byte by byte, up to the empty line.
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+
+        let head = String::from_utf8(head).unwrap();
+        assert!(head.starts_with("HTTP/1.1 101"), "{}", head);
+
+        for message in ["hello", "raw world"] {
+            stream.write_all(message.as_bytes()).await.unwrap();
+            let mut buf = vec![0; message.len()];
+            stream.read_exact(&mut buf).await.unwrap();
+            assert_eq!(buf, message.as_bytes());
+        }
+    }
+}
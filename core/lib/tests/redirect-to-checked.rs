@@ -0,0 +1,71 @@
+#[macro_use] extern crate rocket;
+
+use rocket::response::Redirect;
+
+#[get("/self")]
+fn to_self() -> Redirect {
+    Redirect::to_checked(uri!("/self"))
+}
+
+#[get("/absolute")]
+fn to_absolute() -> Redirect {
+    Redirect::to_checked(uri!("http://rocket.rs/absolute"))
+}
+
+#[get("/go?<to>")]
+fn go(to: String) -> Redirect {
+    Redirect::to_checked(to)
+}
+
+mod redirect_to_checked_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+    use rocket::http::{RawStr, Status};
+    use rocket::http::uri::Host;
+
+    fn client() -> Client {
+        Client::debug_with(routes![to_self, to_absolute, go]).unwrap()
+    }
+
+    fn absolute_with_host(client: &Client, host: &'static str) -> (Status, Option<String>) {
+        let mut request = client.get("/absolute");
+        request.inner_mut().set_host(Host::parse(host).unwrap());
+        let response = request.dispatch();
+        let location = response.headers().get_one("Location").map(|s| s.to_string());
+        (response.status(), location)
+    }
+
+    fn go_to(client: &Client, to: &str) -> (Status, Option<String>) {
+        let uri = format!("/go?to={}", RawStr::new(to).percent_encode());
+        let response = client.get(uri).dispatch();
+        let location = response.headers().get_one("Location").map(|s| s.to_string());
+        (response.status(), location)
+    }
+
+    #[test]
+    fn self_redirect_is_caught() {
+        let client = client();
+        let response = client.get("/self").dispatch();
+        assert_eq!(response.status(), Status::LoopDetected);
+        assert!(response.headers().get_one("Location").is_none());
+
+        assert_eq!(absolute_with_host(&client, "rocket.rs").0, Status::LoopDetected);
+
+        assert_eq!(go_to(&client, "#top").0, Status::LoopDetected);
+    }
+
+    #[test]
+    fn normal_redirect_passes_through() {
+        let client = client();
+        let (status, location) = absolute_with_host(&client, "rocket.rs:8000");
+        assert_eq!(status, Status::SeeOther);
+        assert_eq!(location.unwrap(), "http://rocket.rs/absolute");
+
+        for to in ["/elsewhere", "/go", "/go/", "?page=2", "https://rocket.rs/go"] {
+            let (status, location) = go_to(&client, to);
+            assert_eq!(status, Status::SeeOther, "{}", to);
+            assert_eq!(location.unwrap(), to);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+#[macro_use] extern crate rocket;
+
+use rocket::request::{self, Request, FromRequest};
+use rocket::outcome::{IntoOutcome, try_outcome};
+use rocket::http::Status;
+
+struct Claims {
+    user: String,
+}
+
+struct Authenticated;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Authenticated {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        let user = try_outcome!(req.headers().get_one("Authorization").or_forward(()));
+        req.extensions().insert(Claims { user: user.into() });
+        request::Outcome::Success(Authenticated)
+    }
+}
+
+struct User(String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for User {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.extensions().get::<Claims>()
+            .map(|claims| User(claims.user.clone()))
+            .into_outcome((Status::Unauthorized, ()))
+    }
+}
+
+#[get("/")]
+fn index(_auth: Authenticated, user: User) -> String {
+    user.0
+}
+
+#[get("/unauthenticated")]
+fn unauthenticated(user: User) -> String {
+    user.0
+}
+
+mod request_extensions_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+    use rocket::http::Header;
+
+    #[test]
+    fn guard_reads_value_inserted_by_earlier_guard() {
+        let client = Client::debug_with(routes![index, unauthenticated]).unwrap();
+        let response = client.get("/")
+            .header(Header::new("Authorization", "alice"))
+            .dispatch();
+
+        assert_eq!(response.into_string().unwrap(), "alice");
+    }
+
+    #[test]
+    fn value_absent_unless_inserted() {
+        let client = Client::debug_with(routes![index, unauthenticated]).unwrap();
+        let response = client.get("/unauthenticated")
+            .header(Header::new("Authorization", "alice"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
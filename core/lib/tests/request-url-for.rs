@@ -0,0 +1,87 @@
+use std::net::SocketAddr;
+
+use rocket::{uri, Config};
+use rocket::http::Header;
+use rocket::http::uri::{Absolute, Host};
+use rocket::local::blocking::Client;
+
+fn client(config: Config) -> Client {
+    Client::debug(rocket::custom(config)).unwrap()
+}
+
+mod request_url_for_tests {
+    use super::*;
+
+    const PROXY: &str = "10.0.0.1:4000";
+
+    fn proxied() -> Config {
+        Config { trusted_proxies: vec!["10.0.0.1".parse().unwrap()], ..Config::debug_default() }
+    }
+
+    #[test]
+    fn public_url_is_used() {
+        let public_url = Absolute::parse("https://public-host").ok();
+        let client = client(Config { public_url, ..proxied() });
+        let mut req = client.get("/");
+        req.set_host(Host::from(uri!("127.0.0.1:8000")));
+        assert_eq!(req.url_for(uri!("/a/b?c")).unwrap(), uri!("https://public-host/a/b?c"));
+
+        let req = req.remote(PROXY.parse().unwrap())
+            .header(Header::new("X-Forwarded-Host", "other-host"));
+        assert_eq!(req.url_for(uri!("/a")).unwrap(), uri!("https://public-host/a"));
+    }
+
+    #[test]
+    fn public_url_path_is_prefixed() {
+        let public_url = Absolute::parse("https://public-host:8443/app/").ok();
+        let client = client(Config { public_url, ..Config::debug_default() });
+        let req = client.get("/");
+        assert_eq!(req.url_for(uri!("/a")).unwrap(), uri!("https://public-host:8443/app/a"));
+    }
+
+    #[test]
+    fn forwarded_headers_from_trusted_proxy() {
+        let client = client(proxied());
+        let mut req = client.get("/")
+            .remote(PROXY.parse().unwrap())
+            .header(Header::new("X-Forwarded-Proto", "https"))
+            .header(Header::new("X-Forwarded-Host", "public-host, inner-proxy"));
+
+        req.set_host(Host::from(uri!("127.0.0.1:8000")));
+
+        assert_eq!(req.url_for(uri!("/a/b")).unwrap(), uri!("https://public-host/a/b"));
+    }
+
+    #[test]
+    fn forwarded_headers_from_untrusted_peer_are_ignored() {
+        let client = client(proxied());
+        let remote: SocketAddr = "10.0.0.2:4000".parse().unwrap();
+        let mut req = client.get("/")
+            .remote(remote)
+            .header(Header::new("X-Forwarded-Proto", "https"))
+            .header(Header::new("X-Forwarded-Host", "public-host"));
+
+        req.set_host(Host::from(uri!("127.0.0.1:8000")));
+
+        assert_eq!(req.url_for(uri!("/a")).unwrap(), uri!("http://127.0.0.1:8000/a"));
+    }
+
+    #[test]
+    fn invalid_forwarded_values_fall_back() {
+        let client = client(proxied());
+        let mut req = client.get("/")
+            .remote(PROXY.parse().unwrap())
+            .header(Header::new("X-Forwarded-Proto", "javascript"))
+            .header(Header::new("X-Forwarded-Host", "bad host/"));
+
+        req.set_host(Host::from(uri!("internal")));
+
+        assert_eq!(req.url_for(uri!("/a")).unwrap(), uri!("http://internal/a"));
+    }
+
+    #[test]
+    fn unknown_host_is_none() {
+        let client = client(proxied());
+        assert!(client.get("/").url_for(uri!("/a")).is_none());
+    }
+}
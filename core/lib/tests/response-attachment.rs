@@ -0,0 +1,60 @@
+#[macro_use] extern crate rocket;
+
+use rocket::response::Attachment;
+use rocket::response::content::RawText;
+
+#[get("/ascii")]
+fn ascii() -> Attachment<RawText<&'static str>> {
+    Attachment::new("report.csv", RawText("a,b\n1,2\n"))
+}
+
+#[get("/quoted")]
+fn quoted() -> Attachment<&'static str> {
+    Attachment::new(r#"my "best" \ report.txt"#, "hi")
+}
+
+#[get("/utf8")]
+fn utf8() -> Attachment<&'static str> {
+    Attachment::new("résumé 日本.pdf", "hi")
+}
+
+#[get("/injection")]
+fn injection() -> Attachment<&'static str> {
+    Attachment::new("a\r\nX-Injected: yes", "hi")
+}
+
+mod response_attachment_tests {
+    use super::*;
+
+    use rocket::http::ContentType;
+    use rocket::local::blocking::Client;
+
+    fn disposition(client: &Client, uri: &'static str) -> String {
+        let response = client.get(uri).dispatch();
+        response.headers().get_one("Content-Disposition").unwrap().to_string()
+    }
+
+    #[test]
+    fn attachment_sets_content_disposition() {
+        let rocket = rocket::build().mount("/", routes![ascii, quoted, utf8, injection]);
+        let client = Client::debug(rocket).unwrap();
+
+        let response = client.get("/ascii").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::Plain));
+        assert_eq!(response.headers().get_one("Content-Disposition"),
+            Some("attachment; filename=\"report.csv\"; filename*=UTF-8''report.csv"));
+        assert_eq!(response.into_string().unwrap(), "a,b\n1,2\n");
+
+        assert_eq!(disposition(&client, "/quoted"),
+            r#"attachment; filename="my \"best\" \\ report.txt"; "#.to_string()
+            + "filename*=UTF-8''my%20%22best%22%20%5C%20report.txt");
+
+        assert_eq!(disposition(&client, "/utf8"),
+            "attachment; filename=\"r_sum_ __.pdf\"; \
+            filename*=UTF-8''r%C3%A9sum%C3%A9%20%E6%97%A5%E6%9C%AC.pdf");
+
+        assert_eq!(disposition(&client, "/injection"),
+            "attachment; filename=\"a__X-Injected: yes\"; \
+            filename*=UTF-8''a%0D%0AX-Injected%3A%20yes");
+    }
+}
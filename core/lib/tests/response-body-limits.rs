@@ -0,0 +1,48 @@
+use std::io::Cursor;
+
+use rocket::Response;
+use rocket::data::ToByteUnit;
+
+fn streamed(body: &'static str) -> Response<'static> {
+    Response::build().streamed_body(Cursor::new(body)).max_chunk_size(3).finalize()
+}
+
+#[rocket::async_test]
+async fn collect_streamed_body_within_limit() {
+    let body = streamed("Hello, world!").body_mut().take();
+    let string = body.into_string(13.bytes()).await.unwrap();
+    assert_eq!(string.value, "Hello, world!");
+    assert!(string.is_complete());
+
+    let body = streamed("Hello, world!").body_mut().take();
+    let bytes = body.into_bytes(1.kibibytes()).await.unwrap();
+    assert_eq!(bytes.value, b"Hello, world!");
+    assert!(bytes.is_complete());
+}
+
+#[rocket::async_test]
+async fn collect_streamed_body_over_limit() {
+    let body = streamed("Hello, world!").body_mut().take();
+    let string = body.into_string(5.bytes()).await.unwrap();
+    assert_eq!(string.value, "Hello");
+    assert_eq!(string.n.written, 5);
+    assert!(!string.is_complete());
+
+    let body = streamed("Hello, world!").body_mut().take();
+    let bytes = body.into_bytes(0.bytes()).await.unwrap();
+    assert!(bytes.value.is_empty());
+    assert!(!bytes.is_complete());
+}
+
+#[rocket::async_test]
+async fn collect_empty_and_sized_bodies() {
+    let body = Response::new().body_mut().take();
+    let bytes = body.into_bytes(0.bytes()).await.unwrap();
+    assert!(bytes.value.is_empty());
+    assert!(bytes.is_complete());
+
+    let mut response = Response::build().sized_body(None, Cursor::new("sized")).finalize();
+    let string = response.body_mut().take().into_string(1.kibibytes()).await.unwrap();
+    assert_eq!(string.value, "sized");
+    assert!(string.is_complete());
+}
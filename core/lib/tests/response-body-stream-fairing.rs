@@ -0,0 +1,75 @@
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Response};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::futures::stream::{self, StreamExt};
+use rocket::http::ContentType;
+use rocket::http::hyper::body::Bytes;
+use rocket::response::content::RawHtml;
+
+const FOOTER: &str = "<footer>served by rocket</footer>";
+
+struct Footer;
+
+#[rocket::async_trait]
+impl Fairing for Footer {
+    fn info(&self) -> Info {
+        Info { name: "HTML Footer", kind: Kind::Response }
+    }
+
+    async fn on_response<'r>(&self, _: &'r Request<'_>, res: &mut Response<'r>) {
+        if res.content_type() == Some(ContentType::HTML) {
+            res.map_body_stream(|body| {
+                body.chain(stream::once(async { Ok(Bytes::from(FOOTER)) }))
+            });
+        }
+    }
+}
+
+#[get("/html")]
+fn html() -> RawHtml<&'static str> {
+    RawHtml("<p>hello</p>")
+}
+
+#[get("/text")]
+fn text() -> &'static str {
+    "hello"
+}
+
+mod response_body_stream_fairing_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![html, text])
+            .attach(Footer);
+
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn fairing_appends_footer_to_html() {
+        let client = client();
+        let response = client.get("/html").dispatch();
+        assert_eq!(response.body().preset_size(), None);
+        assert!(response.headers().get_one("Content-Length").is_none());
+        assert_eq!(response.into_string().unwrap(), format!("<p>hello</p>{}", FOOTER));
+    }
+
+    #[test]
+    fn other_bodies_are_untouched() {
+        let client = client();
+        let response = client.get("/text").dispatch();
+        assert_eq!(response.body().preset_size(), Some(5));
+        assert_eq!(response.into_string().unwrap(), "hello");
+    }
+
+    #[test]
+    fn head_request_has_no_body() {
+        let client = client();
+        let response = client.head("/html").dispatch();
+        assert!(response.into_bytes().unwrap_or_default().is_empty());
+    }
+}
@@ -0,0 +1,55 @@
+#[macro_use] extern crate rocket;
+
+use std::io;
+
+use rocket::{Response, State};
+use rocket::http::{ContentType, Status};
+use rocket::response::BufferedResponse;
+
+fn streamed() -> Response<'static> {
+    Response::build()
+        .status(Status::Accepted)
+        .header(ContentType::Plain)
+        .raw_header("X-Replay", "yes")
+        .streamed_body(io::Cursor::new("streamed and buffered"))
+        .finalize()
+}
+
+#[get("/")]
+fn replay(cached: &State<BufferedResponse>) -> BufferedResponse {
+    cached.inner().clone()
+}
+
+mod response_buffer_tests {
+    use super::*;
+
+    use rocket::data::ToByteUnit;
+    use rocket::local::asynchronous::Client;
+
+    #[rocket::async_test]
+    async fn buffered_response_replays() {
+        let buffered = streamed().buffer(1.kibibytes()).await.unwrap();
+        assert_eq!(buffered.status(), Status::Accepted);
+        assert_eq!(buffered.body(), Some(&b"streamed and buffered"[..]));
+
+        let rocket = rocket::build().manage(buffered).mount("/", routes![replay]);
+        let client = Client::debug(rocket).await.unwrap();
+        for _ in 0..2 {
+            let response = client.get("/").dispatch().await;
+            assert_eq!(response.status(), Status::Accepted);
+            assert_eq!(response.content_type(), Some(ContentType::Plain));
+            assert_eq!(response.headers().get_one("X-Replay"), Some("yes"));
+            assert_eq!(response.body().preset_size(), Some(21));
+            assert_eq!(response.into_string().await.unwrap(), "streamed and buffered");
+        }
+    }
+
+    #[rocket::async_test]
+    async fn buffer_respects_limit() {
+        let err = streamed().buffer(8.bytes()).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let buffered = streamed().buffer(21.bytes()).await.unwrap();
+        assert_eq!(buffered.body().map(|b| b.len()), Some(21));
+    }
+}
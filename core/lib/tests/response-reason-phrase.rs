@@ -0,0 +1,76 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use rocket::{Request, Response};
+use rocket::http::Status;
+use rocket::response::{self, Responder};
+
+struct Unprocessable(&'static str);
+
+impl<'r> Responder<'r, 'static> for Unprocessable {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .status(Status::UnprocessableEntity)
+            .reason_phrase(self.0)
+            .ok()
+    }
+}
+
+#[get("/invalid")]
+fn invalid() -> Unprocessable {
+    Unprocessable("Validation Failed")
+}
+
+#[get("/bad")]
+fn bad() -> Unprocessable {
+    Unprocessable("Bad\r\nX-Injected: yes")
+}
+
+#[get("/overridden")]
+fn overridden() -> Unprocessable {
+    Unprocessable("Validation Failed")
+}
+
+#[get("/plain")]
+fn plain() -> Status {
+    Status::UnprocessableEntity
+}
+
+mod response_reason_phrase_tests {
+    use super::*;
+
+    use rocket::fairing::AdHoc;
+
+    use crate::common::get;
+
+    async fn launch() -> u16 {
+        let rocket = rocket::build()
+            .mount("/", routes![invalid, bad, plain, overridden])
+            .attach(AdHoc::on_response("Override", |req, res| Box::pin(async move {
+                if req.uri().path() == "/overridden" {
+                    res.set_status(Status::InternalServerError);
+                }
+            })));
+
+        crate::common::launch(rocket).await
+    }
+
+    #[rocket::async_test]
+    async fn custom_reason_phrase_is_written() {
+        let port = launch().await;
+
+        let response = get(port, "/invalid").await;
+        assert!(response.starts_with("HTTP/1.1 422 Validation Failed\r\n"), "{}", response);
+
+        let response = get(port, "/plain").await;
+        assert!(response.starts_with("HTTP/1.1 422 Unprocessable Entity\r\n"), "{}", response);
+
+        let response = get(port, "/bad").await;
+        assert!(response.starts_with("HTTP/1.1 422 Unprocessable Entity\r\n"), "{}", response);
+        assert!(!response.contains("X-Injected"), "{}", response);
+
+        let response = get(port, "/overridden").await;
+        assert!(response.starts_with("HTTP/1.1 500 Internal Server Error\r\n"), "{}", response);
+    }
+}
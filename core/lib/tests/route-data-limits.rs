@@ -0,0 +1,66 @@
+#[macro_use] extern crate rocket;
+
+use rocket::data::{Data, ToByteUnit};
+
+#[post("/default", data = "<body>")]
+fn default(body: String) -> String {
+    body
+}
+
+#[post("/small", data = "<body>", limit = "small")]
+fn small(body: String) -> String {
+    body
+}
+
+#[post("/unknown", data = "<body>", limit = "unknown")]
+fn unknown(body: String) -> String {
+    body
+}
+
+#[post("/raw", data = "<data>", limit = "small")]
+fn raw(data: Data<'_>) -> String {
+    data.limit().map(|limit| limit.to_string()).unwrap_or_default()
+}
+
+mod route_data_limits_tests {
+    use super::*;
+
+    use rocket::Config;
+    use rocket::data::Limits;
+    use rocket::local::blocking::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        let limits = Limits::default().limit("small", 4.bytes());
+        let config = Config { limits, ..Config::debug_default() };
+        let rocket = rocket::custom(config).mount("/", routes![default, small, unknown, raw]);
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn route_limit_overrides_guard_default() {
+        let client = client();
+        let response = client.post("/default").body("hello, world").dispatch();
+        assert_eq!(response.into_string().unwrap(), "hello, world");
+
+        let response = client.post("/small").body("hi").dispatch();
+        assert_eq!(response.into_string().unwrap(), "hi");
+
+        let response = client.post("/small").body("hello, world").dispatch();
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+    }
+
+    #[test]
+    fn unconfigured_route_limit_falls_back_to_guard_default() {
+        let client = client();
+        let response = client.post("/unknown").body("hello, world").dispatch();
+        assert_eq!(response.into_string().unwrap(), "hello, world");
+    }
+
+    #[test]
+    fn route_limit_is_exposed_to_data() {
+        let client = client();
+        let response = client.post("/raw").body("hello, world").dispatch();
+        assert_eq!(response.into_string().unwrap(), 4.bytes().to_string());
+    }
+}
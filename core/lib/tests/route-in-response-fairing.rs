@@ -0,0 +1,63 @@
+#[macro_use] extern crate rocket;
+
+use rocket::outcome::Outcome;
+use rocket::request::{self, Request, FromRequest};
+
+struct Never;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Never {
+    type Error = ();
+
+    async fn from_request(_: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        Outcome::Forward(())
+    }
+}
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[get("/forward")]
+fn forward(_never: Never) -> &'static str {
+    unreachable!()
+}
+
+mod route_in_response_fairing_tests {
+    use super::*;
+
+    use rocket::fairing::AdHoc;
+    use rocket::http::Status;
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::build()
+            .mount("/", routes![index, forward])
+            .attach(AdHoc::on_response("Route Name", |req, res| Box::pin(async move {
+                let name = req.route().and_then(|r| r.name.as_deref()).unwrap_or("none");
+                res.set_raw_header("X-Route", name.to_string());
+            })));
+
+        Client::debug(rocket).unwrap()
+    }
+
+    #[test]
+    fn response_fairing_sees_matched_route() {
+        let client = client();
+
+        let response = client.get("/").dispatch();
+        assert_eq!(response.headers().get_one("X-Route"), Some("index"));
+
+        let response = client.head("/").dispatch();
+        assert_eq!(response.headers().get_one("X-Route"), Some("index"));
+    }
+
+    #[test]
+    fn forwarded_route_is_not_reported() {
+        let client = client();
+        let response = client.get("/forward").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.headers().get_one("X-Route"), Some("none"));
+    }
+}
@@ -0,0 +1,65 @@
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+struct Db;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Db {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> request::Outcome<Self, ()> {
+        req.record_timing("db", Duration::from_millis(5));
+        request::Outcome::Success(Db)
+    }
+}
+
+#[get("/")]
+fn index(_db: Db) -> &'static str {
+    "hi"
+}
+
+mod server_timing_tests {
+    use super::*;
+
+    use rocket::{Config, Build, Rocket};
+    use rocket::fairing::AdHoc;
+    use rocket::local::blocking::Client;
+
+    fn rocket(server_timing: bool) -> Rocket<Build> {
+        let config = Config { server_timing, ..Config::debug_default() };
+        rocket::custom(config)
+            .mount("/", routes![index])
+            .attach(AdHoc::on_request("Timer", |req, _| Box::pin(async move {
+                req.record_timing("auth", Duration::from_micros(1500));
+            })))
+    }
+
+    #[test]
+    fn server_timing_reports_recorded_durations() {
+        let client = Client::debug(rocket(true)).unwrap();
+        let response = client.get("/").dispatch();
+        let header = response.headers().get_one("Server-Timing").unwrap();
+
+        let timings: Vec<_> = header.split(", ").collect();
+        assert_eq!(timings.len(), 3);
+        assert_eq!(timings[0], "auth;dur=1.500");
+        assert_eq!(timings[1], "db;dur=5.000");
+
+        let total = timings[2].strip_prefix("total;dur=").unwrap();
+        assert!(total.parse::<f64>().unwrap() >= 0.0);
+    }
+
+    #[test]
+    fn server_timing_is_disabled_by_default() {
+        assert!(!Config::debug_default().server_timing);
+        assert!(!Config::release_default().server_timing);
+
+        let client = Client::debug(rocket(false)).unwrap();
+        let response = client.get("/").dispatch();
+        assert!(response.headers().get_one("Server-Timing").is_none());
+    }
+}
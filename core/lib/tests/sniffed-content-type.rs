@@ -0,0 +1,60 @@
+#[macro_use] extern crate rocket;
+
+use rocket::http::ContentType;
+use rocket::response::content::Sniffed;
+
+const PNG: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n', 0, 0, 0, 13];
+
+#[get("/png")]
+fn png() -> Sniffed<Vec<u8>> {
+    Sniffed(PNG.to_vec())
+}
+
+#[get("/text")]
+fn text() -> Sniffed<&'static [u8]> {
+    Sniffed("héllo, wörld!\n".as_bytes())
+}
+
+#[get("/binary")]
+fn binary() -> Sniffed<Vec<u8>> {
+    Sniffed(vec![0, 1, 2, 3])
+}
+
+#[get("/explicit")]
+fn explicit() -> Sniffed<String> {
+    // `String` sets `text/plain`, which takes precedence over the sniffed PDF.
+    Sniffed("%PDF-1.7".into())
+}
+
+mod sniffed_content_type_tests {
+    use super::*;
+
+    use rocket::local::blocking::Client;
+
+    fn client() -> Client {
+        Client::debug_with(routes![png, text, binary, explicit]).unwrap()
+    }
+
+    #[test]
+    fn sniffs_png_and_text() {
+        let client = client();
+
+        let response = client.get("/png").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::PNG));
+        assert_eq!(response.into_bytes().unwrap(), PNG);
+
+        let response = client.get("/text").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::Text));
+    }
+
+    #[test]
+    fn unrecognized_or_explicit_types_are_kept() {
+        let client = client();
+
+        let response = client.get("/binary").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::Binary));
+
+        let response = client.get("/explicit").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::Plain));
+    }
+}
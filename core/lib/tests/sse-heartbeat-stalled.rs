@@ -0,0 +1,44 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use std::time::Duration;
+
+use rocket::response::stream::{Event, EventStream};
+
+#[get("/")]
+fn stalled() -> EventStream![] {
+    let stream = EventStream! {
+        yield Event::data("start");
+        rocket::futures::future::pending::<()>().await;
+    };
+
+    stream.heartbeat(Duration::from_millis(100))
+}
+
+mod sse_heartbeat_stalled_tests {
+    use super::*;
+
+    use rocket::tokio::time::{timeout, Instant};
+    use rocket::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[rocket::async_test]
+    async fn heartbeats_are_sent_while_producer_is_stalled() {
+        let port = common::launch(rocket::build().mount("/", routes![stalled])).await;
+        let mut stream = common::connect(port).await;
+        stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").await.unwrap();
+
+        let deadline = Instant::now() + Duration::from_millis(550);
+        let mut response = String::new();
+        let mut buf = [0; 256];
+        while let Ok(read) = timeout(deadline - Instant::now(), stream.read(&mut buf)).await {
+            let n = read.unwrap();
+            assert_ne!(n, 0, "connection closed: {:?}", response);
+            response.push_str(std::str::from_utf8(&buf[..n]).unwrap());
+        }
+
+        assert!(response.contains("data:start\n"), "{:?}", response);
+        let heartbeats = response.matches(":\n").count();
+        assert!(heartbeats >= 3, "got {} beat(s): {:?}", heartbeats, response);
+    }
+}
@@ -0,0 +1,76 @@
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+#[get("/")]
+fn index() -> &'static str { "index" }
+
+#[get("/foo")]
+fn foo() -> &'static str { "foo" }
+
+#[post("/bar")]
+fn bar() -> &'static str { "bar" }
+
+#[get("/files/<path..>")]
+fn files(path: PathBuf) -> String { path.display().to_string() }
+
+mod trailing_slash_tests {
+    use super::*;
+
+    use rocket::Config;
+    use rocket::config::TrailingSlash;
+    use rocket::local::blocking::Client;
+    use rocket::http::Status;
+
+    fn client(trailing_slash: TrailingSlash) -> Client {
+        let config = Config { trailing_slash, ..Config::debug_default() };
+        let rocket = rocket::custom(config).mount("/", routes![index, foo, bar, files]);
+        Client::debug(rocket).unwrap()
+    }
+
+    fn get(client: &Client, uri: &str) -> (Status, Option<String>) {
+        let response = client.get(uri.to_string()).dispatch();
+        (response.status(), response.into_string())
+    }
+
+    fn assert_unaffected(client: &Client) {
+        assert_eq!(get(client, "/"), (Status::Ok, Some("index".into())));
+        assert_eq!(get(client, "/foo"), (Status::Ok, Some("foo".into())));
+        assert_eq!(get(client, "/files/a/b"), (Status::Ok, Some("a/b".into())));
+        assert_eq!(get(client, "/files/a/b/"), (Status::Ok, Some("a/b".into())));
+        assert_eq!(client.post("/bar").dispatch().status(), Status::Ok);
+    }
+
+    #[test]
+    fn ignore_matches_either() {
+        let client = client(TrailingSlash::Ignore);
+        assert_unaffected(&client);
+        assert_eq!(get(&client, "/foo/"), (Status::Ok, Some("foo".into())));
+        assert_eq!(client.post("/bar/").dispatch().status(), Status::Ok);
+    }
+
+    #[test]
+    fn strict_rejects_trailing_slash() {
+        let client = client(TrailingSlash::Strict);
+        assert_unaffected(&client);
+        assert_eq!(get(&client, "/foo/").0, Status::NotFound);
+        assert_eq!(client.post("/bar/").dispatch().status(), Status::NotFound);
+    }
+
+    #[test]
+    fn redirect_to_path_without_slash() {
+        let client = client(TrailingSlash::Redirect);
+        assert_unaffected(&client);
+
+        let response = client.get("/foo/?a=b").dispatch();
+        assert_eq!(response.status(), Status::PermanentRedirect);
+        assert_eq!(response.headers().get_one("Location"), Some("/foo?a=b"));
+
+        let response = client.post("/bar//").dispatch();
+        assert_eq!(response.status(), Status::PermanentRedirect);
+        assert_eq!(response.headers().get_one("Location"), Some("/bar"));
+
+        assert_eq!(get(&client, "/nope/").0, Status::NotFound);
+        assert_eq!(client.get("/bar/").dispatch().status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,85 @@
+use rocket::form::{FromForm, QueryExt};
+use rocket::http::uri::{Absolute, Origin};
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Page<'r> {
+    page: usize,
+    sort: &'r str,
+}
+
+#[test]
+fn query_as_parses_absolute_and_origin_queries() {
+    let expected = Page { page: 2, sort: "asc" };
+
+    let uri = Absolute::parse("https://rocket.rs/items?page=2&sort=asc").unwrap();
+    assert_eq!(uri.query_as::<Page<'_>>().unwrap(), expected);
+
+    let uri = Origin::parse("/items?page=2&sort=asc").unwrap();
+    assert_eq!(uri.query_as::<Page<'_>>().unwrap(), expected);
+}
+
+#[test]
+fn query_as_reports_errors() {
+    let uri = Absolute::parse("https://rocket.rs/items?page=two&sort=asc").unwrap();
+    assert!(uri.query_as::<Page<'_>>().is_err());
+
+    let uri = Origin::parse("/items").unwrap();
+    let errors = uri.query_as::<Page<'_>>().unwrap_err();
+    assert_eq!(errors.len(), 2);
+}
+
+#[derive(Debug, PartialEq, FromForm)]
+struct Filter<'r> {
+    page: usize,
+    tag: Vec<&'r str>,
+}
+
+#[test]
+fn query_as_resolves_duplicate_scalars_by_policy() {
+    use rocket::form::{Options, Duplicates, error::ErrorKind};
+
+    let uri = Origin::parse("/items?page=1&tag=a&page=2").unwrap();
+
+    let filter = uri.query_as::<Filter<'_>>().unwrap();
+    assert_eq!(filter.page, 1);
+
+    let opts = Options { duplicates: Duplicates::First, ..Options::Lenient };
+    assert_eq!(uri.query_as_with::<Filter<'_>>(opts).unwrap().page, 1);
+
+    let opts = Options { duplicates: Duplicates::Last, ..Options::Lenient };
+    assert_eq!(uri.query_as_with::<Filter<'_>>(opts).unwrap().page, 2);
+
+    let opts = Options { duplicates: Duplicates::Reject, ..Options::Lenient };
+    let errors = uri.query_as_with::<Filter<'_>>(opts).unwrap_err();
+    assert!(errors.iter().any(|e| e.kind == ErrorKind::Duplicate));
+
+    assert!(uri.query_as_with::<Filter<'_>>(Options::Strict).is_err());
+}
+
+#[test]
+fn query_as_collects_repeated_keys() {
+    use rocket::form::{Options, Duplicates};
+
+    let uri = Absolute::parse("https://rocket.rs/items?tag=a&page=1&tag=b&tag=c").unwrap();
+    let expected = Filter { page: 1, tag: vec!["a", "b", "c"] };
+    assert_eq!(uri.query_as::<Filter<'_>>().unwrap(), expected);
+
+    let opts = Options { duplicates: Duplicates::Reject, ..Options::Lenient };
+    assert_eq!(uri.query_as_with::<Filter<'_>>(opts).unwrap(), expected);
+}
+
+#[test]
+fn lenient_field_under_strict_options_takes_first_duplicate() {
+    use rocket::form::{Lenient, Options, Duplicates};
+
+    #[derive(FromForm)]
+    struct Outer {
+        page: Lenient<usize>,
+    }
+
+    assert_eq!(Duplicates::default(), Duplicates::First);
+
+    let uri = Origin::parse("/items?page=1&page=2").unwrap();
+    let outer = uri.query_as_with::<Outer>(Options::Strict).unwrap();
+    assert_eq!(*outer.page, 1);
+}
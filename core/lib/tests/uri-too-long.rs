@@ -0,0 +1,49 @@
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use rocket::http::Status;
+
+#[get("/<_path..>")]
+fn index(_path: std::path::PathBuf) -> &'static str {
+    "ok"
+}
+
+#[catch(414)]
+fn too_long() -> &'static str {
+    "too long"
+}
+
+mod uri_too_long_tests {
+    use super::*;
+
+    use rocket::Config;
+
+    use crate::common::get;
+
+    async fn launch() -> u16 {
+        let config = Config { max_uri_length: 64, ..Config::debug_default() };
+        let rocket = rocket::custom(config)
+            .mount("/", routes![index])
+            .register("/", catchers![too_long]);
+
+        crate::common::launch(rocket).await
+    }
+
+    #[rocket::async_test]
+    async fn over_length_uri_is_rejected() {
+        let port = launch().await;
+
+        let path = format!("/{}", "a".repeat(63));
+        let response = get(port, &path).await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+
+        let response = get(port, &format!("{}b", path)).await;
+        assert!(response.starts_with(&format!("HTTP/1.1 {}\r\n", Status::UriTooLong)));
+        assert!(response.ends_with("too long"));
+
+        // The path and query are limited together.
+        let response = get(port, &format!("/a?{}", "q".repeat(62))).await;
+        assert!(response.starts_with("HTTP/1.1 414 "), "{}", response);
+    }
+}